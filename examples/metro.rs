@@ -35,7 +35,8 @@ fn main() -> ! {
         &mut peripherals.NVMCTRL,
     );
     let mut pins = bsp::Pins::new(peripherals.PORT);
-    let mut delay = Delay::new(core.SYST, &mut clocks);
+    // the driver owns the delay and uses it for its own timing
+    let delay = Delay::new(core.SYST, &mut clocks);
 
     let i2c = bsp::i2c_master(
         &mut clocks,
@@ -53,7 +54,7 @@ fn main() -> ! {
     // may not calibrate with defaults, ideally these should be computed from
     // the datasheet
     let calib = CalibrationParams::default();
-    let mut haptic = Drv2605l::new(i2c, Calibration::Auto(calib), false).unwrap();
+    let mut haptic = Drv2605l::new(i2c, delay, Calibration::Auto(calib), false).unwrap();
     dbgprint!("sucessfully calibrated device");
 
     // An LRA motor with configuration and Auto calibration
@@ -61,7 +62,7 @@ fn main() -> ! {
     // calib.rated_voltage = 0x3E;
     // calib.overdrive_voltage_clamp = 0x8C;
     // calib.drive_time = 0x13;
-    // let mut haptic = Drv2605l::new(i2c, Calibration::Auto(calib), true).unwrap();
+    // let mut haptic = Drv2605l::new(i2c, delay, Calibration::Auto(calib), true).unwrap();
 
     // print the sucessful calibration values so you can hardcode them later
     // let params = haptic.calibration().unwrap();
@@ -75,6 +76,7 @@ fn main() -> ! {
     // and use hardcoded ones them instead of auto calibration like this
     // let mut haptic = Drv2605l::new(
     //     i2c,
+    //     delay,
     //     //from the
     //     Calibration::Load(drv2605::LoadParams {
     //         compenstation: 0x3E,
@@ -100,9 +102,9 @@ fn main() -> ! {
         Effect::ShortDoubleClickStrongOne100,
         Effect::Delays(100), //100 * 10ms or 1000ms
         Effect::StrongClick100,
-        Effect::Stop, //stop early
-        Effect::Stop, //stop early
-        Effect::Stop, //stop early
+        Effect::Delays(100), //wait another second before we start again
+        Effect::Stop,        //stop early
+        Effect::Stop,        //stop early
     ];
     haptic.set_rom(&roms).unwrap();
 
@@ -111,17 +113,12 @@ fn main() -> ! {
     loop {
         // fire
         haptic.set_go().unwrap();
-        // you dont to, but we can poll the device until the sequence finishes
-        while haptic.go().unwrap() {}
-
-        // wait another second after that before we start again
-        delay.delay_ms(255u8);
-        delay.delay_ms(255u8);
-        delay.delay_ms(255u8);
-        delay.delay_ms(255u8);
+        // poll the device until the sequence, trailing wait included, finishes
+        haptic.wait_until_idle().unwrap();
     }
 
-    // or rtp mode, or software pwm over i2c, might look like this instead
+    // or rtp mode, or software pwm over i2c, might look like this instead,
+    // given a second delay since the driver owns the first
     // haptic.set_standby(false).unwrap();
     // haptic.set_mode(Mode::RealTimePlayback).unwrap();
     // loop {
@@ -144,7 +141,9 @@ fn main() -> ! {
 
     // or pwm mode, assuming pwm had been configured and was outputting to the
     // in/trig pin
-    // haptic.set_mode(Mode::Pwm).unwrap();
+    // haptic
+    //     .set_mode(Mode::Pwm(PwmParams { input_freq_hint: 20_000 }))
+    //     .unwrap();
     // haptic.set_standby(false).unwrap();
     // loop{
     //       delay.delay_ms(255u8);
@@ -0,0 +1,141 @@
+use crate::{BrakeMode, Drv2605l, DrvError, Effect, NoPin};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+/// Something for the `Arbiter` to play
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum HapticRequest {
+    /// A single rom `Effect`
+    Effect(Effect),
+    /// Rom effects played in order, which may run past the 8 hardware slots
+    Pattern(&'static [Effect]),
+}
+
+/// Shares a single motor between features that each want to play haptics.
+/// There are `LEVELS` priority levels with 0 the lowest, each holding a single
+/// pending request so a newer request replaces an older one at the same level.
+/// A request preempts whatever lower priority request is playing, which is
+/// stopped and dropped rather than resumed.
+///
+/// Playback isn't awaited, so call `service` regularly to start the next
+/// pending request once the motor goes idle. Patterns longer than 8 slots are
+/// also played on by `service`, a run of 8 slots at a time.
+pub struct Arbiter<I2C, E, const LEVELS: usize, D, EN = NoPin>
+where
+    I2C: I2c<Error = E>,
+{
+    haptic: Drv2605l<I2C, E, D, EN>,
+    pending: [Option<HapticRequest>; LEVELS],
+    playing: Option<usize>,
+    remaining: &'static [Effect],
+}
+
+impl<I2C, E, const LEVELS: usize, D, EN> Arbiter<I2C, E, LEVELS, D, EN>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+    EN: OutputPin,
+{
+    /// Wrap a device that is already in `Mode::Rom` and out of standby
+    pub fn new(haptic: Drv2605l<I2C, E, D, EN>) -> Self {
+        Self {
+            haptic,
+            pending: [None; LEVELS],
+            playing: None,
+            remaining: &[],
+        }
+    }
+
+    /// Queue `request` at `priority` and start it right away if nothing of the
+    /// same or higher priority is playing. Returns
+    /// `DrvError::InvalidParameter` if `priority` isn't below `LEVELS`.
+    pub async fn submit(
+        &mut self,
+        priority: usize,
+        request: HapticRequest,
+    ) -> Result<(), DrvError<E>> {
+        let slot = self
+            .pending
+            .get_mut(priority)
+            .ok_or(DrvError::InvalidParameter)?;
+        *slot = Some(request);
+
+        self.service().await
+    }
+
+    /// Drop the pending request at `priority`, if any. A request that has
+    /// already started playing isn't affected.
+    pub fn cancel(&mut self, priority: usize) {
+        if let Some(slot) = self.pending.get_mut(priority) {
+            *slot = None;
+        }
+    }
+
+    /// Check on playback and start the highest priority pending request if
+    /// the motor is idle or only playing something of lower priority
+    pub async fn service(&mut self) -> Result<(), DrvError<E>> {
+        let idle = self.playing.is_some() && !self.haptic.go().await?;
+        if idle && self.remaining.is_empty() {
+            self.playing = None;
+        }
+
+        let next = self
+            .pending
+            .iter()
+            .rposition(Option::is_some)
+            .filter(|&next| self.playing.is_none_or(|playing| next > playing));
+        let Some(next) = next else {
+            // nothing preempts the playing pattern, so carry on with it
+            if self.playing.is_some() && idle {
+                return self.play_remaining().await;
+            }
+            return Ok(());
+        };
+
+        if self.playing.is_some() {
+            self.haptic.stop(BrakeMode::Active).await?;
+        }
+
+        self.remaining = &[];
+        match self.pending[next].take() {
+            Some(HapticRequest::Effect(effect)) => {
+                self.haptic.set_rom_single(effect).await?;
+                self.haptic.set_go().await?;
+            }
+            Some(HapticRequest::Pattern(slots)) => {
+                self.remaining = slots;
+                self.play_remaining().await?;
+            }
+            None => unreachable!("pending request was just found"),
+        }
+        self.playing = Some(next);
+
+        Ok(())
+    }
+
+    /// The priority of the request currently playing, as of the last `service`
+    pub fn playing(&self) -> Option<usize> {
+        self.playing
+    }
+
+    /// Access the wrapped device
+    pub fn haptic(&mut self) -> &mut Drv2605l<I2C, E, D, EN> {
+        &mut self.haptic
+    }
+
+    /// Give back the wrapped device
+    pub fn into_inner(self) -> Drv2605l<I2C, E, D, EN> {
+        self.haptic
+    }
+
+    /* Private calls */
+
+    /// Start the next run of up to 8 slots of the playing pattern
+    async fn play_remaining(&mut self) -> Result<(), DrvError<E>> {
+        let (chunk, rest) = self.remaining.split_at(self.remaining.len().min(8));
+        self.remaining = rest;
+        self.haptic.play_sequence_now(chunk).await
+    }
+}
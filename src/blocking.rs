@@ -0,0 +1,458 @@
+//! A blocking driver for firmware without an async executor, built on the
+//! `embedded_hal` traits rather than `embedded_hal_async`. It shares the
+//! register definitions, configuration types and register sequencing with
+//! the async driver and covers its core surface, the same calls minus the
+//! `.await`s.
+
+#[cfg(feature = "rtp")]
+use crate::registers::RealTimePlaybackInputReg;
+use crate::registers::{
+    self, AutoCalibrationCompensationBackEmfReg, AutoCalibrationCompensationReg, Control4Reg,
+    FeedbackControlReg, GoReg, LraPeriodReg, ModeReg, Register, StatusReg,
+};
+#[cfg(feature = "rom")]
+use crate::registers::{Control5Reg, LibrarySelectionReg};
+use crate::{
+    plan, Calibration, DrvError, LoadParams, Mode, NoPin, OperatingMode, Status, DEFAULT_ADDRESS,
+    ENABLE_SETTLE_US, GO_MODES, GO_POLL_INTERVAL_MS, RESET_POLL_ATTEMPTS, RESET_POLL_INTERVAL_US,
+    RESET_SETTLE_US, SELF_TIMED_GO_TIMEOUT_MS,
+};
+#[cfg(feature = "rom")]
+use crate::{Effect, Library, RomParams, IDLE_TIMEOUT_MS, TRIGGERED_MODES};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{Error as _, OutputPin};
+use embedded_hal::i2c::I2c;
+
+/// A Texas instruments Drv2605 haptic motor driver for LRA and ERM motors,
+/// blocking on every I2C transaction. See the async `crate::Drv2605l` for the
+/// full story on each call.
+pub struct Drv2605l<I2C, E, D, EN = NoPin>
+where
+    I2C: I2c<Error = E>,
+{
+    i2c: I2C,
+    delay: D,
+    en: Option<EN>,
+    address: u8,
+    lra: bool,
+    #[cfg(feature = "rom")]
+    library: Option<Library>,
+    strict: bool,
+}
+
+impl<I2C, E, D> Drv2605l<I2C, E, D>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Returns a calibrated Drv2605l device configured to standby mode for
+    /// power savings, after resetting every register to its default
+    pub fn new(
+        i2c: I2C,
+        delay: D,
+        calibration: Calibration,
+        lra: bool,
+    ) -> Result<Self, DrvError<E>> {
+        Self::init(i2c, delay, None, DEFAULT_ADDRESS, calibration, lra)
+    }
+
+    /// Like `new`, but for a device answering at `address` rather than
+    /// `DEFAULT_ADDRESS`
+    pub fn new_with_address(
+        i2c: I2C,
+        delay: D,
+        address: u8,
+        calibration: Calibration,
+        lra: bool,
+    ) -> Result<Self, DrvError<E>> {
+        Self::init(i2c, delay, None, address, calibration, lra)
+    }
+}
+
+#[allow(unused)]
+impl<I2C, E, D, EN> Drv2605l<I2C, E, D, EN>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+    EN: OutputPin,
+{
+    /// Like `new_with_address`, but drives the EN pin, which is pulled high
+    /// before anything else, see `crate::Drv2605l::enable`
+    pub fn new_with_enable_pin(
+        i2c: I2C,
+        delay: D,
+        en: EN,
+        address: u8,
+        calibration: Calibration,
+        lra: bool,
+    ) -> Result<Self, DrvError<E>> {
+        Self::init(i2c, delay, Some(en), address, calibration, lra)
+    }
+
+    /// Power the device up by driving the EN pin high, see
+    /// `crate::Drv2605l::enable`
+    pub fn enable(&mut self) -> Result<(), DrvError<E>> {
+        let Some(en) = self.en.as_mut() else {
+            return self.set_standby(false);
+        };
+
+        en.set_high().map_err(|e| DrvError::EnablePin(e.kind()))?;
+        self.delay.delay_us(ENABLE_SETTLE_US);
+        Ok(())
+    }
+
+    /// Power the device down by driving the EN pin low, see
+    /// `crate::Drv2605l::disable`
+    pub fn disable(&mut self) -> Result<(), DrvError<E>> {
+        let Some(en) = self.en.as_mut() else {
+            return self.set_standby(true);
+        };
+
+        en.set_low().map_err(|e| DrvError::EnablePin(e.kind()))?;
+        self.forget_device_state();
+        Ok(())
+    }
+
+    /// Performs the equivalent operation of power cycling the device, see
+    /// `crate::Drv2605l::reset`
+    pub fn reset(&mut self) -> Result<(), DrvError<E>> {
+        let mut mode = ModeReg::default();
+        mode.set_dev_reset(true);
+        self.write(mode)?;
+        self.forget_device_state();
+
+        self.delay.delay_us(RESET_SETTLE_US);
+        for _ in 0..RESET_POLL_ATTEMPTS {
+            // the device may not answer at all while it's still resetting
+            if let Ok(mode) = self.read::<ModeReg>() {
+                if !mode.dev_reset() {
+                    return Ok(());
+                }
+            }
+            self.delay.delay_us(RESET_POLL_INTERVAL_US);
+        }
+
+        Err(DrvError::ResetTimeout)
+    }
+
+    /// Select a mode of operation, see `crate::Drv2605l::set_mode`
+    pub fn set_mode(&mut self, mode: Mode) -> Result<(), DrvError<E>> {
+        let mut low = [0u8; 3];
+        self.read_burst(plan::ModeRegs::LOW, &mut low)?;
+        let mut control = [0u8; 5];
+        self.read_burst(plan::ModeRegs::CONTROL, &mut control)?;
+
+        let plan = plan::mode(&mode, self.lra, &plan::ModeRegs::new(low, control))?;
+        self.write_batch(plan.writes.as_slice())?;
+        #[cfg(feature = "rom")]
+        if let Some((library, _)) = plan.rom {
+            self.library = Some(library);
+        }
+        Ok(())
+    }
+
+    /// Get the operating mode the device is currently in, whoever set it
+    pub fn current_mode(&mut self) -> Result<OperatingMode, DrvError<E>> {
+        Ok(self.read::<ModeReg>()?.mode())
+    }
+
+    /// Enable strict checking when queueing rom effects, see
+    /// `crate::Drv2605l::set_strict`
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Apply rom timing offsets and the playback interval
+    #[cfg(feature = "rom")]
+    pub fn set_rom_params(&mut self, params: &RomParams) -> Result<(), DrvError<E>> {
+        let ctrl5: Control5Reg = self.read()?;
+        let mut writes = plan::Writes::new();
+        plan::rom_params(&mut writes, params, ctrl5);
+        self.write_batch(writes.as_slice())
+    }
+
+    /// Select the rom `Library` effects are played from
+    #[cfg(feature = "rom")]
+    pub fn set_library(&mut self, library: Library) -> Result<(), DrvError<E>> {
+        let library = plan::resolve_library(library, self.lra)?;
+        self.debug_assert_mode(&TRIGGERED_MODES, "set_library")?;
+        let lib: LibrarySelectionReg = self.read()?;
+        let mut writes = plan::Writes::new();
+        plan::select_library(&mut writes, library, lib);
+        self.write_batch(writes.as_slice())?;
+        self.library = Some(library);
+        Ok(())
+    }
+
+    /// Sets up to 8 Effects to play in order when `set_go` is called. Stops
+    /// playing early if `Effect::None` is used.
+    #[cfg(feature = "rom")]
+    pub fn set_rom(&mut self, roms: &[Effect; 8]) -> Result<(), DrvError<E>> {
+        self.load_waveforms(roms, false, "set_rom")
+    }
+
+    /// Set a single `Effect` to play when `set_go` is called
+    #[cfg(feature = "rom")]
+    pub fn set_rom_single(&mut self, rom: Effect) -> Result<(), DrvError<E>> {
+        self.load_waveforms(&[rom], false, "set_rom_single")
+    }
+
+    /// Set the duty cycle for `Mode::RealTimePlayback`
+    #[cfg(feature = "rtp")]
+    pub fn set_rtp(&mut self, duty: u8) -> Result<(), DrvError<E>> {
+        self.check_mode(&[registers::Mode::RealTimePlayback])?;
+        self.debug_assert_mode(&[registers::Mode::RealTimePlayback], "set_rtp")?;
+        self.write(RealTimePlaybackInputReg(duty))
+    }
+
+    /// Set the drive for `Mode::RealTimePlaybackSigned`, see
+    /// `crate::Drv2605l::set_rtp_signed`
+    #[cfg(feature = "rtp")]
+    pub fn set_rtp_signed(&mut self, value: i8) -> Result<(), DrvError<E>> {
+        self.check_mode(&[registers::Mode::RealTimePlayback])?;
+        self.debug_assert_mode(&[registers::Mode::RealTimePlayback], "set_rtp_signed")?;
+        self.write(RealTimePlaybackInputReg(value as u8))
+    }
+
+    /// Trigger a GO for whatever mode is enabled
+    pub fn set_go(&mut self) -> Result<(), DrvError<E>> {
+        self.debug_assert_mode(&GO_MODES, "set_go")?;
+        let mut go: GoReg = self.read()?;
+        go.set_go(true);
+        self.write(go)
+    }
+
+    /// Get the go bit, which clears when playback has completed
+    pub fn go(&mut self) -> Result<bool, DrvError<E>> {
+        Ok(self.read::<GoReg>()?.go())
+    }
+
+    /// Block until the GO bit clears, returning `DrvError::Timeout` if it's
+    /// still set after the longest sequence the sequencer can hold
+    #[cfg(feature = "rom")]
+    pub fn wait_until_idle(&mut self) -> Result<(), DrvError<E>> {
+        self.wait_for_idle(IDLE_TIMEOUT_MS)
+    }
+
+    /// Enable or disable low power standby mode
+    pub fn set_standby(&mut self, enable: bool) -> Result<(), DrvError<E>> {
+        let mut mode: ModeReg = self.read()?;
+        mode.set_standby(enable);
+        self.write(mode)
+    }
+
+    /// Get the status register bits
+    pub fn status(&mut self) -> Result<u8, DrvError<E>> {
+        Ok(self.read::<StatusReg>()?.value())
+    }
+
+    /// Get the status register decoded into a `Status`
+    pub fn read_status(&mut self) -> Result<Status, DrvError<E>> {
+        Ok(self.read::<StatusReg>()?.into())
+    }
+
+    /// Get the calibrated `LoadParams` to hardcode via `Calibration::Load`
+    pub fn calibration(&mut self) -> Result<LoadParams, DrvError<E>> {
+        let feedback: FeedbackControlReg = self.read()?;
+        let compenstation: AutoCalibrationCompensationReg = self.read()?;
+        let back_emf: AutoCalibrationCompensationBackEmfReg = self.read()?;
+
+        Ok(LoadParams {
+            back_emf_gain: feedback.bemf_gain(),
+            compenstation: compenstation.value(),
+            back_emf: back_emf.value(),
+        })
+    }
+
+    /// Confirm the calibration registers hold exactly `expected`, see
+    /// `crate::Drv2605l::verify_calibration`
+    pub fn verify_calibration(&mut self, expected: &LoadParams) -> Result<(), DrvError<E>> {
+        if self.calibration()? != *expected {
+            return Err(DrvError::CalibrationMismatch);
+        }
+        Ok(())
+    }
+
+    /// Give back the I2C bus, delay and EN pin if any
+    pub fn release(self) -> (I2C, D, Option<EN>) {
+        (self.i2c, self.delay, self.en)
+    }
+
+    /* Private calls */
+
+    /// Shared by the constructors
+    fn init(
+        i2c: I2C,
+        delay: D,
+        en: Option<EN>,
+        address: u8,
+        calibration: Calibration,
+        lra: bool,
+    ) -> Result<Self, DrvError<E>> {
+        let mut haptic = Self {
+            i2c,
+            delay,
+            en,
+            address,
+            lra,
+            #[cfg(feature = "rom")]
+            library: None,
+            strict: false,
+        };
+        if haptic.en.is_some() {
+            haptic.enable()?;
+        }
+        if haptic.read::<StatusReg>()?.device_id() != 7 {
+            return Err(DrvError::WrongDeviceId);
+        }
+        haptic.reset()?;
+
+        match calibration {
+            Calibration::Otp => {
+                if !haptic.read::<Control4Reg>()?.otp_status() {
+                    return Err(DrvError::OTPNotProgrammed);
+                }
+            }
+            Calibration::Load(c) => haptic.set_calibration(c)?,
+            Calibration::Auto(c) => {
+                haptic.write_batch(&c.to_register_image(lra))?;
+                haptic.calibrate()?;
+            }
+        }
+
+        haptic.set_standby(true)?;
+
+        Ok(haptic)
+    }
+
+    /// Drop everything tracked about the device's registers once they're lost
+    /// to a reset or power down
+    fn forget_device_state(&mut self) {
+        #[cfg(feature = "rom")]
+        {
+            self.library = None;
+        }
+    }
+
+    /// In strict mode return `DrvError::WrongMode` unless the device is in one
+    /// of the `allowed` modes
+    fn check_mode(&mut self, allowed: &[registers::Mode]) -> Result<(), DrvError<E>> {
+        if !self.strict {
+            return Ok(());
+        }
+
+        if !allowed.contains(&self.read::<ModeReg>()?.mode()) {
+            return Err(DrvError::WrongMode);
+        }
+        Ok(())
+    }
+
+    /// In debug builds assert the device is in one of the `allowed` modes
+    /// before `operation`. The mode read and the check compile away entirely
+    /// in release builds.
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    fn debug_assert_mode(
+        &mut self,
+        allowed: &[registers::Mode],
+        operation: &str,
+    ) -> Result<(), DrvError<E>> {
+        #[cfg(debug_assertions)]
+        {
+            let mode = self.read::<ModeReg>()?.mode();
+            debug_assert!(
+                allowed.contains(&mode),
+                "{} while in {:?} mode",
+                operation,
+                mode
+            );
+        }
+        Ok(())
+    }
+
+    /// Check `slots` against strict mode and the current mode, then load them
+    /// into the waveform sequencer as described by `plan::waveforms`
+    #[cfg(feature = "rom")]
+    fn load_waveforms(
+        &mut self,
+        slots: &[Effect],
+        go: bool,
+        operation: &str,
+    ) -> Result<(), DrvError<E>> {
+        let writes = plan::waveforms(slots, go)?;
+        plan::check_effects(self.strict, self.library, slots)?;
+        self.check_mode(&TRIGGERED_MODES)?;
+        self.debug_assert_mode(&TRIGGERED_MODES, operation)?;
+        self.write_batch(writes.as_slice())
+    }
+
+    fn wait_for_idle(&mut self, timeout_ms: u32) -> Result<(), DrvError<E>> {
+        for _ in 0..timeout_ms.div_ceil(GO_POLL_INTERVAL_MS) {
+            if !self.go()? {
+                return Ok(());
+            }
+            self.delay.delay_ms(GO_POLL_INTERVAL_MS);
+        }
+
+        if self.go()? {
+            return Err(DrvError::Timeout);
+        }
+        Ok(())
+    }
+
+    fn set_calibration(&mut self, load: LoadParams) -> Result<(), DrvError<E>> {
+        let feedback: FeedbackControlReg = self.read()?;
+        self.write_batch(plan::calibration(&load, feedback).as_slice())
+    }
+
+    fn calibrate(&mut self) -> Result<LoadParams, DrvError<E>> {
+        let mode: ModeReg = self.read()?;
+        self.write(plan::calibration_mode(mode))?;
+
+        self.set_go()?;
+        self.wait_for_idle(SELF_TIMED_GO_TIMEOUT_MS)?;
+
+        let status = StatusReg(self.status()?);
+        let load = self.calibration()?;
+        let period = match self.lra {
+            true => Some(self.read::<LraPeriodReg>()?.value()),
+            false => None,
+        };
+        plan::check_calibration(&status, &load, period)?;
+        Ok(load)
+    }
+
+    fn write<REG>(&mut self, register: REG) -> Result<(), DrvError<E>>
+    where
+        REG: Register,
+    {
+        self.write_burst(&[REG::ADDRESS, register.value()])
+    }
+
+    fn write_burst(&mut self, buf: &[u8]) -> Result<(), DrvError<E>> {
+        self.i2c.write(self.address, buf).map_err(DrvError::I2c)
+    }
+
+    /// Write (address, value) pairs in order, see `plan::bursts`
+    fn write_batch(&mut self, writes: &[(u8, u8)]) -> Result<(), DrvError<E>> {
+        for burst in plan::bursts(writes) {
+            self.write_burst(burst.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn read<REG>(&mut self) -> Result<REG, DrvError<E>>
+    where
+        REG: Register + From<u8>,
+    {
+        let mut buf = [0u8; 1];
+        self.read_burst(REG::ADDRESS, &mut buf)?;
+        Ok(buf[0].into())
+    }
+
+    fn read_burst(&mut self, address: u8, buf: &mut [u8]) -> Result<(), DrvError<E>> {
+        self.i2c
+            .write_read(self.address, &[address], buf)
+            .map_err(DrvError::I2c)
+    }
+}
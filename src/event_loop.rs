@@ -0,0 +1,149 @@
+use crate::{BrakeMode, Drv2605l, DrvError, Effect, Library, Mode, RomParams, IDLE_TIMEOUT_MS};
+use core::convert::Infallible;
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::Receiver;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+/// Something for `Drv2605l::run_event_loop` to do
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum HapticEvent {
+    /// Play a single rom `Effect`
+    PlayEffect(Effect),
+    /// Play rom effects in order, which may run past the 8 hardware slots
+    PlayPattern(&'static [Effect]),
+    /// Drive continuously at an rtp intensity between 0.0 and 1.0, see
+    /// `Drv2605l::set_intensity`
+    SetIntensity(f32),
+    /// Stop whatever is playing with `BrakeMode::Active`
+    Stop,
+}
+
+/// Which output the event loop last set the device up for
+#[derive(Clone, Copy, PartialEq)]
+enum Output {
+    Unknown,
+    Rom,
+    Rtp,
+}
+
+impl<I2C, E, D, EN> Drv2605l<I2C, E, D, EN>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+    EN: OutputPin,
+{
+    /// Serve `HapticEvent`s from an embassy channel forever, switching
+    /// between rom and rtp playback as needed. Rom effects play from `library`
+    /// configured with `params`, as with `Mode::Rom`. A new event preempts rom
+    /// playback still in progress, stopping it first. Only returns on error.
+    pub async fn run_event_loop<M, const N: usize>(
+        &mut self,
+        receiver: Receiver<'_, M, HapticEvent, N>,
+        library: Library,
+        params: RomParams,
+    ) -> Result<Infallible, DrvError<E>>
+    where
+        M: RawMutex,
+    {
+        self.set_standby(false).await?;
+
+        let rom = Mode::Rom(library, params);
+        let mut output = Output::Unknown;
+        let mut next = None;
+        loop {
+            let event = match next.take() {
+                Some(event) => event,
+                None => receiver.receive().await,
+            };
+            next = self
+                .handle_event(event, rom, &mut output, &receiver)
+                .await?;
+        }
+    }
+
+    /// Act on one event, returning any event that preempted it
+    async fn handle_event<M, const N: usize>(
+        &mut self,
+        event: HapticEvent,
+        rom: Mode,
+        output: &mut Output,
+        receiver: &Receiver<'_, M, HapticEvent, N>,
+    ) -> Result<Option<HapticEvent>, DrvError<E>>
+    where
+        M: RawMutex,
+    {
+        match event {
+            HapticEvent::PlayEffect(effect) => {
+                self.enter_output(output, Output::Rom, rom).await?;
+                self.play_sequence_now(&[effect]).await?;
+                self.wait_preemptible(receiver).await
+            }
+            HapticEvent::PlayPattern(slots) => {
+                self.enter_output(output, Output::Rom, rom).await?;
+                for chunk in slots.chunks(8) {
+                    self.play_sequence_now(chunk).await?;
+                    if let Some(preempted) = self.wait_preemptible(receiver).await? {
+                        return Ok(Some(preempted));
+                    }
+                }
+                Ok(None)
+            }
+            HapticEvent::SetIntensity(level) => {
+                self.enter_output(output, Output::Rtp, rom).await?;
+                self.set_intensity(level).await?;
+                Ok(None)
+            }
+            HapticEvent::Stop => {
+                self.stop(BrakeMode::Active).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Switch the device to `wanted` unless it's already there, using `rom`
+    /// for rom playback
+    async fn enter_output(
+        &mut self,
+        output: &mut Output,
+        wanted: Output,
+        rom: Mode,
+    ) -> Result<(), DrvError<E>> {
+        if *output == wanted {
+            return Ok(());
+        }
+
+        match wanted {
+            Output::Rtp => self.set_mode(Mode::RealTimePlayback).await?,
+            Output::Rom | Output::Unknown => self.set_mode(rom).await?,
+        }
+        *output = wanted;
+        Ok(())
+    }
+
+    /// Poll the GO bit until playback finishes or another event arrives, in
+    /// which case playback is stopped and the event returned. Gives up with
+    /// `DrvError::Timeout` like `wait_until_idle`.
+    async fn wait_preemptible<M, const N: usize>(
+        &mut self,
+        receiver: &Receiver<'_, M, HapticEvent, N>,
+    ) -> Result<Option<HapticEvent>, DrvError<E>>
+    where
+        M: RawMutex,
+    {
+        let mut preempted = None;
+        let interrupted = self
+            .poll_go(IDLE_TIMEOUT_MS, || {
+                preempted = receiver.try_receive().ok();
+                preempted.is_some()
+            })
+            .await?;
+
+        if interrupted {
+            self.stop(BrakeMode::Active).await?;
+        }
+        Ok(preempted)
+    }
+}
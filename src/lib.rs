@@ -1,243 +1,1081 @@
 #![no_std]
 
+#[cfg(feature = "rom")]
+mod arbiter;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "embassy")]
+mod event_loop;
+mod plan;
 mod registers;
+#[cfg(feature = "rom")]
+pub use arbiter::{Arbiter, HapticRequest};
+use core::convert::Infallible;
+use core::ops::RangeInclusive;
+#[cfg(feature = "rom")]
+use core::sync::atomic::AtomicU8;
+#[cfg(any(feature = "rom", feature = "rtp"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "rom")]
+use core::time::Duration;
+use embedded_hal::digital::{Error as _, ErrorKind as PinErrorKind, ErrorType, OutputPin};
+use embedded_hal_async::delay::DelayNs;
 use embedded_hal_async::i2c::I2c;
+#[cfg(feature = "embassy")]
+pub use event_loop::HapticEvent;
+#[cfg(feature = "audio")]
 use registers::{
-    AutoCalibrationCompensationBackEmfReg, AutoCalibrationCompensationReg,
-    BrakeTimeOffsetReg, Control1Reg, Control2Reg, Control3Reg, Control4Reg, Control5Reg,
-    FeedbackControlReg, GoReg, LibrarySelectionReg, ModeReg, OverdriveClampReg,
-    OverdriveTimeOffsetReg, RatedVoltageReg, RealTimePlaybackInputReg, Register, StatusReg,
-    SustainTimeOffsetNegativeReg, SustainTimeOffsetPositiveReg, Waveform0Reg,
+    AudioToVibeControlReg, AudioToVibeMaxDriveReg, AudioToVibeMaxInputReg, AudioToVibeMinDriveReg,
+    AudioToVibeMinInputReg,
 };
-pub use registers::{Effect, Library};
+use registers::{
+    AutoCalibrationCompensationBackEmfReg, AutoCalibrationCompensationReg, Control1Reg,
+    Control2Reg, Control3Reg, Control4Reg, Control5Reg, FeedbackControlReg, GoReg,
+    LibrarySelectionReg, LraPeriodReg, ModeReg, OverdriveClampReg, RatedVoltageReg,
+    RealTimePlaybackInputReg, Register, StatusReg, VbatReg,
+};
+#[cfg(feature = "rom")]
+use registers::{BrakeTimeOffsetReg, Waveform0Reg};
+pub use registers::{Effect, Library, Mode as OperatingMode};
 
 /// A Texas instruments Drv2605 haptic motor driver for LRA and ERM motors
-pub struct Drv2605l<I2C, E>
+///
+/// # Cancellation
+///
+/// `Drop` can't be async, so dropping the driver or cancelling the task that
+/// owns it mid playback leaves the device doing whatever it was last told. An
+/// rtp duty or a long rom effect will keep the motor running. Call `stop` in
+/// any cancellation branch, for example when racing playback against a cancel
+/// signal:
+///
+/// ```ignore
+/// match select(haptic.play_effect(effect, CompletionStrategy::Poll), cancel.wait()).await {
+///     Either::First(played) => played?,
+///     Either::Second(_) => haptic.stop(BrakeMode::Active).await?,
+/// }
+/// ```
+pub struct Drv2605l<I2C, E, D, EN = NoPin>
 where
     I2C: I2c<Error = E>,
 {
     i2c: I2C,
+    delay: D,
+    en: Option<EN>,
+    address: u8,
     lra: bool,
+    calibration_source: CalibrationSource,
+    thermal_baseline: Option<u8>,
+    library: Option<Library>,
+    strict: bool,
+    auto_brake: bool,
+    strength_baseline: Option<(u8, u8)>,
+    calibrated_closed_loop: Option<bool>,
+    inter_op_delay_us: u32,
 }
 
-#[allow(unused)]
-impl<I2C, E> Drv2605l<I2C, E>
+impl<I2C, E, D> Drv2605l<I2C, E, D>
 where
     I2C: I2c<Error = E>,
+    D: DelayNs,
 {
     /// Returns a calibrated Drv2605l device configured to standby mode for
     /// power savings. Closed loop is hardcoded for all motors and modes except
-    /// ERM motors in rom mode where open loop is automatically enabled.
+    /// ERM motors in rom mode where open loop is automatically enabled. The
+    /// device is reset first so every register starts from its default.
     ///
     /// Use a `set_mode` and `set_go` to trigger a vibration.
     pub async fn new(
         i2c: I2C,
+        delay: D,
         calibration: Calibration,
         lra: bool,
-    ) -> Result<Self, DrvError> {
-        let mut haptic = Self { i2c, lra };
-        haptic.check_id(7).await?;
+    ) -> Result<Self, DrvError<E>> {
+        Self::init(i2c, delay, None, DEFAULT_ADDRESS, calibration, lra, 0).await
+    }
 
-        // todo reset so registers are defaulted. Currently timing out..  need a
-        // solution for delaying and retrying. Currently we send default values
-        // to all registers we track so were probably fine without it for now
-        // haptic.reset()?;
+    /// A `Drv2605lBuilder` for naming the configuration rather than passing
+    /// it positionally
+    pub fn builder(i2c: I2C, delay: D) -> Drv2605lBuilder<I2C, D> {
+        Drv2605lBuilder::new(i2c, delay)
+    }
 
-        match calibration {
-            // device will get c/alibration values out of the otp if the otp bit is set
-            Calibration::Otp => {
-                if !haptic.is_otp().await? {
-                    return Err(DrvError::OTPNotProgrammed);
-                }
-            }
-            // load up previously calibrated values
-            Calibration::Load(c) => haptic.set_calibration(c).await?,
-            Calibration::Auto(c) => {
-                let mut feedback: FeedbackControlReg = Default::default();
-                let mut ctrl2: Control2Reg = Default::default();
-                let mut ctrl4: Control4Reg = Default::default();
-                let mut ctrl1: Control1Reg = Default::default();
-
-                let mut rated = RatedVoltageReg(c.rated_voltage);
-                let mut clamp = OverdriveClampReg(c.overdrive_voltage_clamp);
-
-                feedback.set_fb_brake_factor(c.brake_factor);
-                feedback.set_loop_gain(c.loop_gain);
-                if (lra) {
-                    feedback.set_n_erm_lra(true);
-                }
-                ctrl2.set_sample_time(c.lra_sample_time);
-                ctrl2.set_blanking_time(c.lra_blanking_time);
-                ctrl2.set_idiss_time(c.lra_idiss_time);
-                ctrl4.set_auto_cal_time(c.auto_cal_time);
-                ctrl4.set_zc_det_time(c.lra_zc_det_time);
-                ctrl1.set_drive_time(c.drive_time);
-
-                haptic.write(feedback).await?;
-                haptic.write(ctrl2).await?;
-                haptic.write(ctrl4).await?;
-                haptic.write(rated).await?;
-                haptic.write(clamp).await?;
-                haptic.write(ctrl1).await?;
-                haptic.calibrate().await?;
-            }
+    /// Like `new`, but for a device answering at `address` rather than
+    /// `DEFAULT_ADDRESS`, such as one of several behind an address translator
+    pub async fn new_with_address(
+        i2c: I2C,
+        delay: D,
+        address: u8,
+        calibration: Calibration,
+        lra: bool,
+    ) -> Result<Self, DrvError<E>> {
+        Self::init(i2c, delay, None, address, calibration, lra, 0).await
+    }
+
+    /// Like `new`, but waits `inter_op_delay_us` microseconds on `delay`
+    /// before every I2C transaction, construction included, to go easy on a
+    /// shared bus with peripherals that misbehave when it's hammered back to
+    /// back
+    pub async fn new_with_inter_op_delay(
+        i2c: I2C,
+        delay: D,
+        calibration: Calibration,
+        lra: bool,
+        inter_op_delay_us: u32,
+    ) -> Result<Self, DrvError<E>> {
+        Self::init(
+            i2c,
+            delay,
+            None,
+            DEFAULT_ADDRESS,
+            calibration,
+            lra,
+            inter_op_delay_us,
+        )
+        .await
+    }
+
+    /// Returns a device configured from a `ConfigSnapshot` captured with
+    /// `snapshot_config` on an earlier boot, skipping calibration for a fast
+    /// re-init. Only the device id is checked before the snapshot is restored,
+    /// and the device is left in standby whatever mode the snapshot was taken
+    /// in. Returns `DrvError::WrongMotorType` if the snapshot was taken for
+    /// the other kind of motor.
+    pub async fn new_preconfigured(
+        i2c: I2C,
+        delay: D,
+        snapshot: ConfigSnapshot,
+        lra: bool,
+    ) -> Result<Self, DrvError<E>> {
+        if FeedbackControlReg(snapshot.feedback).n_erm_lra() != lra {
+            return Err(DrvError::WrongMotorType);
         }
 
-        haptic.set_standby(true).await?;
+        let mut haptic = Self {
+            i2c,
+            delay,
+            en: None,
+            address: DEFAULT_ADDRESS,
+            lra,
+            calibration_source: CalibrationSource::Snapshot,
+            thermal_baseline: None,
+            library: None,
+            strict: false,
+            auto_brake: false,
+            strength_baseline: None,
+            calibrated_closed_loop: None,
+            inter_op_delay_us: 0,
+        };
+        haptic.check_id(7).await?;
+
+        let mut mode = ModeReg(snapshot.mode);
+        mode.set_standby(true);
+        let snapshot = ConfigSnapshot {
+            mode: mode.value(),
+            ..snapshot
+        };
+        haptic.restore_config(&snapshot).await?;
 
         Ok(haptic)
     }
+}
+
+#[allow(unused)]
+impl<I2C, E, D, EN> Drv2605l<I2C, E, D, EN>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+    EN: OutputPin,
+{
+    /// Change the pause before each I2C transaction, zero to disable it
+    pub fn set_inter_op_delay_us(&mut self, inter_op_delay_us: u32) {
+        self.inter_op_delay_us = inter_op_delay_us;
+    }
 
-    pub async fn set_mode(&mut self, mode: Mode) -> Result<(), DrvError> {
-        let mut m: ModeReg = self.read().await?;
+    /// Give back the I2C bus, delay and EN pin if any, such as to hand a
+    /// shared bus to another driver. The device is left doing whatever it was
+    /// last told.
+    pub fn release(self) -> (I2C, D, Option<EN>) {
+        (self.i2c, self.delay, self.en)
+    }
 
-        let mut ctrl3: Control3Reg = self.read().await?;
+    /// Power the device up by driving the EN pin high, then wait out its
+    /// roughly 250us wake up before the first I2C transaction. The device
+    /// comes back from a `disable` with its registers at their defaults, so
+    /// reconfigure it afterward. Without an EN pin this falls back to
+    /// `set_standby(false)`.
+    pub async fn enable(&mut self) -> Result<(), DrvError<E>> {
+        let Some(en) = self.en.as_mut() else {
+            return self.set_standby(false).await;
+        };
+
+        en.set_high().map_err(|e| DrvError::EnablePin(e.kind()))?;
+        self.delay.delay_us(ENABLE_SETTLE_US).await;
+        Ok(())
+    }
 
-        match mode {
-            Mode::Pwm => {
-                // unset in case coming from rom mode
-                if !self.lra {
-                    ctrl3.set_erm_open_loop(false);
-                }
-                ctrl3.set_n_pwm_analog(false);
-                self.write(ctrl3).await?;
+    /// Power the device down by driving the EN pin low, a true shutdown
+    /// drawing far less than register standby. Without an EN pin this falls
+    /// back to `set_standby(true)`.
+    pub async fn disable(&mut self) -> Result<(), DrvError<E>> {
+        let Some(en) = self.en.as_mut() else {
+            return self.set_standby(true).await;
+        };
+
+        en.set_low().map_err(|e| DrvError::EnablePin(e.kind()))?;
+        self.forget_device_state();
+        Ok(())
+    }
 
-                m.set_mode(registers::Mode::PwmInputAndAnalogInput as u8);
-                self.write(m).await
+    /// Performs the equivalent operation of power cycling the device. Any
+    /// playback operations are immediately interrupted, and all registers are
+    /// reset to their default values, leaving the device in standby and in
+    /// need of calibration. Returns `DrvError::ResetTimeout` if the device
+    /// doesn't come back within a few milliseconds.
+    pub async fn reset(&mut self) -> Result<(), DrvError<E>> {
+        let mut mode = ModeReg::default();
+        mode.set_dev_reset(true);
+        self.write(mode).await?;
+        self.forget_device_state();
+
+        self.delay.delay_us(RESET_SETTLE_US).await;
+        for _ in 0..RESET_POLL_ATTEMPTS {
+            // the device may not answer at all while it's still resetting
+            if let Ok(mode) = self.read::<ModeReg>().await {
+                if !mode.dev_reset() {
+                    return Ok(());
+                }
             }
-            Mode::Rom(library, options) => {
-                let mut ctrl5: Control5Reg = self.read().await?;
-                ctrl5.set_playback_interval(options.decrease_playback_interval);
-                self.write(ctrl5).await?;
+            self.delay.delay_us(RESET_POLL_INTERVAL_US).await;
+        }
 
-                let mut overdrive = OverdriveTimeOffsetReg(options.overdrive_time_offset);
-                self.write(overdrive).await?;
+        Err(DrvError::ResetTimeout)
+    }
 
-                let mut sustain_p =
-                    SustainTimeOffsetPositiveReg(options.sustain_positive_offset);
-                self.write(sustain_p).await?;
+    pub async fn set_mode(&mut self, mode: Mode) -> Result<(), DrvError<E>> {
+        let mut low = [0u8; 3];
+        self.read_burst(plan::ModeRegs::LOW, &mut low).await?;
+        let mut control = [0u8; 5];
+        self.read_burst(plan::ModeRegs::CONTROL, &mut control)
+            .await?;
+
+        let plan = plan::mode(&mode, self.lra, &plan::ModeRegs::new(low, control))?;
+        self.write_batch(plan.writes.as_slice()).await?;
+        #[cfg(feature = "rom")]
+        if let Some((library, auto_brake)) = plan.rom {
+            self.library = Some(library);
+            self.auto_brake = auto_brake;
+        }
+        Ok(())
+    }
 
-                let mut sustain_n =
-                    SustainTimeOffsetNegativeReg(options.sustain_negative_offset);
-                self.write(sustain_n).await?;
+    /// Sets up to 8 Effects to play in order when `set_go` is called. Stops
+    /// playing early if `Effect::None` is used. See `set_sequence` for fewer
+    /// than 8 slots.
+    #[cfg(feature = "rom")]
+    pub async fn set_rom(&mut self, roms: &[Effect; 8]) -> Result<(), DrvError<E>> {
+        self.load_waveforms(roms, false, "set_rom").await
+    }
 
-                let mut brake = BrakeTimeOffsetReg(options.brake_time_offset);
-                self.write(brake).await?;
+    /// Load up to 8 `SequenceStep`s to play in order when `set_go` is called,
+    /// terminated with `Effect::Stop` if there are fewer than 8. Returns
+    /// `DrvError::SequenceTooLong` for more than 8 steps and
+    /// `DrvError::InvalidParameter` for a wait over 127.
+    #[cfg(feature = "rom")]
+    pub async fn set_sequence(&mut self, steps: &[SequenceStep]) -> Result<(), DrvError<E>> {
+        if steps.len() > 8 {
+            return Err(DrvError::SequenceTooLong);
+        }
 
-                // erm requires open loop mode
-                if !self.lra {
-                    ctrl3.set_erm_open_loop(true);
-                }
-                self.write(ctrl3).await?;
+        let mut slots = [Effect::Stop; 8];
+        for (slot, step) in slots.iter_mut().zip(steps) {
+            *slot = match *step {
+                SequenceStep::Effect(effect) => effect,
+                SequenceStep::Wait(n) if n > 0x7f => return Err(DrvError::InvalidParameter),
+                SequenceStep::Wait(n) => Effect::Delays(n),
+            };
+        }
+        self.load_waveforms(&slots[..steps.len()], false, "set_sequence")
+            .await
+    }
 
-                let mut lib: LibrarySelectionReg = self.read().await?;
-                lib.set_library_selection(library as u8);
-                self.write(lib).await?;
+    /// Load up to 8 Effects and trigger a GO in a single I2C transaction for
+    /// the lowest latency playback. The waveform sequencer registers sit right
+    /// before the GO register so the write simply runs on into it. Fewer than 8
+    /// effects are terminated with `Effect::Stop`, and more than 8 returns
+    /// `DrvError::SequenceTooLong`. The device should already be in
+    /// `Mode::Rom` and out of standby.
+    #[cfg(feature = "rom")]
+    pub async fn play_sequence_now(&mut self, slots: &[Effect]) -> Result<(), DrvError<E>> {
+        self.load_waveforms(slots, true, "play_sequence_now").await
+    }
 
-                m.set_mode(registers::Mode::InternalTrigger as u8);
-                self.write(m).await
-            }
-            Mode::Analog => {
-                // unset in case coming from rom mode
-                if !self.lra {
-                    ctrl3.set_erm_open_loop(false);
-                }
-                ctrl3.set_n_pwm_analog(true);
-                self.write(ctrl3).await?;
+    /// Enable strict checking when queueing rom effects, catching setups
+    /// where they would silently play nothing. Waveform effects queued while
+    /// the `Library` last selected is `Library::Empty` are rejected with
+    /// `DrvError::EffectLibraryMismatch`, and queueing effects outside of a
+    /// rom capable mode or setting the rtp drive outside of rtp mode returns
+    /// `DrvError::WrongMode`. The mode check costs an extra register read per
+    /// call. Off by default.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
 
-                m.set_mode(registers::Mode::PwmInputAndAnalogInput as u8);
-                self.write(m).await
-            }
-            Mode::RealTimePlayback => {
-                // We won't need to unset as no other modes use this bit
-                ctrl3.set_data_format_rtp(true);
-                // unset in case coming from rom mode
-                if !self.lra {
-                    ctrl3.set_erm_open_loop(false);
-                }
-                self.write(ctrl3).await?;
+    /// Get the currently selected rom `Library`. Every value of the 3 bit
+    /// library field maps to a `Library` so this can't come back unknown.
+    #[cfg(feature = "rom")]
+    pub async fn library(&mut self) -> Result<Library, DrvError<E>> {
+        let lib: LibrarySelectionReg = self.read().await?;
+        Ok(lib.library_selection())
+    }
 
-                m.set_mode(registers::Mode::RealTimePlayback as u8);
-                self.write(m).await
-            }
-        }
+    /// Get the operating mode the device is currently in, whoever set it
+    pub async fn current_mode(&mut self) -> Result<OperatingMode, DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        Ok(mode.mode())
     }
 
-    /// Sets up to 8 Effects to play in order when `set_go` is called. Stops
-    /// playing early if `Effect::None` is used.
-    // todo dont hardcode to 8, pass slice? but then need to assert <=8
-    pub async fn set_rom(&mut self, roms: &[Effect; 8]) -> Result<(), DrvError> {
-        let buf: [u8; 9] = [
-            Waveform0Reg::ADDRESS,
-            roms[0].into(),
-            roms[1].into(),
-            roms[2].into(),
-            roms[3].into(),
-            roms[4].into(),
-            roms[5].into(),
-            roms[6].into(),
-            roms[7].into(),
-        ];
-        self.i2c
-            .write(ADDRESS, &buf)
-            .await
-            .map_err(|_| DrvError::ConnectionError)
+    /// Apply rom timing offsets and the playback interval without going
+    /// through `set_mode`. The four offset registers are contiguous so they're
+    /// written in a single transaction.
+    #[cfg(feature = "rom")]
+    pub async fn set_rom_params(&mut self, params: &RomParams) -> Result<(), DrvError<E>> {
+        self.auto_brake = params.auto_brake;
+
+        let ctrl5: Control5Reg = self.read().await?;
+        let mut writes = plan::Writes::new();
+        plan::rom_params(&mut writes, params, ctrl5);
+        self.write_batch(writes.as_slice()).await
+    }
+
+    /// Switch the rom `Library` without going through `set_mode`, leaving the
+    /// rest of the rom configuration alone
+    #[cfg(feature = "rom")]
+    pub async fn set_library(&mut self, library: Library) -> Result<(), DrvError<E>> {
+        let library = plan::resolve_library(library, self.lra)?;
+        self.debug_assert_mode(&TRIGGERED_MODES, "set_library")
+            .await?;
+        let lib: LibrarySelectionReg = self.read().await?;
+        let mut writes = plan::Writes::new();
+        plan::select_library(&mut writes, library, lib);
+        self.write_batch(writes.as_slice()).await?;
+        self.library = Some(library);
+        Ok(())
+    }
+
+    /// Set the brake time offset for rom effects in milliseconds, rounded to
+    /// the nearest playback interval. `interval_1ms` should match
+    /// `RomParams::decrease_playback_interval`, otherwise each interval is
+    /// 5ms.
+    #[cfg(feature = "rom")]
+    pub async fn set_brake_time_offset_ms(
+        &mut self,
+        ms: u8,
+        interval_1ms: bool,
+    ) -> Result<(), DrvError<E>> {
+        let offset = match interval_1ms {
+            true => ms,
+            false => ((u16::from(ms) + 2) / 5) as u8,
+        };
+        self.write(BrakeTimeOffsetReg(offset)).await
+    }
+
+    /// Get the brake time offset for rom effects in milliseconds, using the
+    /// playback interval currently set on the device
+    #[cfg(feature = "rom")]
+    pub async fn brake_time_offset_ms(&mut self) -> Result<u16, DrvError<E>> {
+        let brake: BrakeTimeOffsetReg = self.read().await?;
+        let ctrl5: Control5Reg = self.read().await?;
+        let interval_ms = match ctrl5.playback_interval() {
+            true => 1,
+            false => 5,
+        };
+        Ok(u16::from(brake.value()) * interval_ms)
     }
 
     /// Set a single `Effect` into rom storage during rom mode when `set_go` is
     /// called
-    pub async fn set_rom_single(&mut self, rom: Effect) -> Result<(), DrvError> {
-        let buf: [u8; 3] = [Waveform0Reg::ADDRESS, rom.into(), Effect::Stop.into()];
-        self.i2c
-            .write(ADDRESS, &buf)
-            .await
-            .map_err(|_| DrvError::ConnectionError)
+    #[cfg(feature = "rom")]
+    pub async fn set_rom_single(&mut self, rom: Effect) -> Result<(), DrvError<E>> {
+        self.load_waveforms(&[rom], false, "set_rom_single").await
     }
 
     /// Change the duty cycle for rtp mode
-    pub async fn set_rtp(&mut self, duty: u8) -> Result<(), DrvError> {
+    #[cfg(feature = "rtp")]
+    pub async fn set_rtp(&mut self, duty: u8) -> Result<(), DrvError<E>> {
+        self.check_mode(&[registers::Mode::RealTimePlayback])
+            .await?;
+        self.debug_assert_mode(&[registers::Mode::RealTimePlayback], "set_rtp")
+            .await?;
         let rtp = RealTimePlaybackInputReg(duty);
         self.write(rtp).await
     }
 
     /// Get the current rtp duty cycle
-    pub async fn rtp(&mut self) -> Result<u8, DrvError> {
+    #[cfg(feature = "rtp")]
+    pub async fn rtp(&mut self) -> Result<u8, DrvError<E>> {
         let rtp: RealTimePlaybackInputReg = self.read().await?;
 
         Ok(rtp.value())
     }
 
-    /// Trigger a GO for whatever mode is enabled
-    pub async fn set_go(&mut self) -> Result<(), DrvError> {
+    /// Change the drive for `Mode::RealTimePlaybackSigned`, where 0 is the
+    /// midpoint with no drive, 127 is full drive and -128 (0x80) is the full
+    /// negative end, which brakes. Don't mix this up with `set_rtp`, where the
+    /// unsigned 0x00 is full braking and 0x80 is about half drive.
+    #[cfg(feature = "rtp")]
+    pub async fn set_rtp_signed(&mut self, value: i8) -> Result<(), DrvError<E>> {
+        self.check_mode(&[registers::Mode::RealTimePlayback])
+            .await?;
+        self.debug_assert_mode(&[registers::Mode::RealTimePlayback], "set_rtp_signed")
+            .await?;
+        self.write(RealTimePlaybackInputReg(value as u8)).await
+    }
+
+    /// Get the current rtp drive as a signed value, see `set_rtp_signed`
+    #[cfg(feature = "rtp")]
+    pub async fn rtp_signed(&mut self) -> Result<i8, DrvError<E>> {
+        let rtp: RealTimePlaybackInputReg = self.read().await?;
+
+        Ok(rtp.value() as i8)
+    }
+
+    /// Set the rtp duty cycle from an intensity between 0.0 and 1.0. Never
+    /// fails on a bad level: anything outside the range is clamped to it and
+    /// NaN is treated as 0.0. See `try_set_intensity` to be told instead.
+    #[cfg(feature = "rtp")]
+    pub async fn set_intensity(&mut self, level: f32) -> Result<(), DrvError<E>> {
+        let level = match level.is_nan() {
+            true => 0.0,
+            false => level.clamp(0.0, 1.0),
+        };
+        self.set_rtp((level * 255.0) as u8).await
+    }
+
+    /// Like `set_intensity` but returns `DrvError::InvalidParameter` for NaN
+    /// or a level outside 0.0 to 1.0 rather than saturating it
+    #[cfg(feature = "rtp")]
+    pub async fn try_set_intensity(&mut self, level: f32) -> Result<(), DrvError<E>> {
+        if !(0.0..=1.0).contains(&level) {
+            return Err(DrvError::InvalidParameter);
+        }
+        self.set_intensity(level).await
+    }
+
+    /// Set the rtp intensity scaled up to make up for a drooping supply so the
+    /// perceived strength stays constant as a battery drains. Output is
+    /// compensated relative to `VBAT_REFERENCE_MV` and never exceeds full
+    /// scale. The supply is only measured while the device is driving so the
+    /// uncompensated intensity is applied first and the supply sampled after a
+    /// short settling delay.
+    #[cfg(feature = "rtp")]
+    pub async fn set_intensity_compensated(&mut self, level: f32) -> Result<(), DrvError<E>> {
+        self.set_intensity(level).await?;
+        self.delay.delay_us(VBAT_SETTLE_US).await;
+
+        let supply = self.supply_voltage_mv().await?;
+        if supply == 0 {
+            // nothing was measured, so leave it uncompensated
+            return Ok(());
+        }
+
+        let scale = f32::from(VBAT_REFERENCE_MV) / f32::from(supply);
+        self.set_intensity(level * scale.max(1.0)).await
+    }
+
+    /// Set the rtp intensity that produces `g` of acceleration on a motor
+    /// described by `model`. Returns `DrvError::InvalidParameter` if the
+    /// acceleration is negative or more than the motor reaches at the rated
+    /// voltage.
+    #[cfg(feature = "rtp")]
+    pub async fn set_target_acceleration(
+        &mut self,
+        g: f32,
+        model: MotorModel,
+    ) -> Result<(), DrvError<E>> {
+        let level = g * model.volts_per_g / model.rated_voltage;
+        if !(0.0..=1.0).contains(&level) {
+            return Err(DrvError::InvalidParameter);
+        }
+
+        self.set_intensity(level).await
+    }
+
+    /// Set the LRA auto-resonance sampling time, 0: 150us, 1: 200us, 2: 250us,
+    /// 3: 300us. The LRA rated voltage formula depends on the sampling time,
+    /// so changing it alone shifts the physical drive voltage. Pass
+    /// `keep_voltage` to also rewrite the rated voltage so it still drives the
+    /// given RMS voltage at the new sampling time. Returns
+    /// `DrvError::InvalidParameter` for an out of range sampling time or an
+    /// unreachable voltage, and `DrvError::WrongMotorType` when keeping the
+    /// voltage of an ERM motor.
+    pub async fn set_sample_time(
+        &mut self,
+        sample_time: u8,
+        keep_voltage: Option<LraVoltage>,
+    ) -> Result<(), DrvError<E>> {
+        if sample_time > 3 {
+            return Err(DrvError::InvalidParameter);
+        }
+        let rated = match keep_voltage {
+            Some(_) if !self.lra => return Err(DrvError::WrongMotorType),
+            Some(voltage) => Some(voltage.rated_voltage(sample_time)?),
+            None => None,
+        };
+
+        let mut ctrl2: Control2Reg = self.read().await?;
+        ctrl2.set_sample_time(sample_time);
+        self.write(ctrl2).await?;
+
+        if let Some(rated) = rated {
+            self.write(RatedVoltageReg(rated)).await?;
+            self.strength_baseline = None;
+        }
+        Ok(())
+    }
+
+    /// Master volume for everything the device plays, rom effects included.
+    /// The rated voltage and overdrive clamp are scaled by `strength`, from
+    /// 0.0 to 1.0, relative to their values on the first call which are taken
+    /// as full strength, or since the voltages were last replaced by
+    /// `set_sample_time`, `set_rated_voltage`, `set_overdrive_clamp` or
+    /// `restore_config`. Scaling is linear in drive voltage. Returns
+    /// `DrvError::InvalidParameter` for a strength out of range.
+    pub async fn set_global_strength(&mut self, strength: f32) -> Result<(), DrvError<E>> {
+        if !(0.0..=1.0).contains(&strength) {
+            return Err(DrvError::InvalidParameter);
+        }
+
+        let (rated, clamp) = match self.strength_baseline {
+            Some(baseline) => baseline,
+            None => {
+                let rated: RatedVoltageReg = self.read().await?;
+                let clamp: OverdriveClampReg = self.read().await?;
+                let baseline = (rated.value(), clamp.value());
+                self.strength_baseline = Some(baseline);
+                baseline
+            }
+        };
+
+        let scale = |value: u8| (f32::from(value) * strength) as u8;
+        self.write(RatedVoltageReg(scale(rated))).await?;
+        self.write(OverdriveClampReg(scale(clamp))).await
+    }
+
+    /// Set the RATED_VOLTAGE register, the full scale closed loop drive. See
+    /// the datasheet for the formulas, which differ between ERM and LRA
+    /// motors. Auto calibration is tuned against the rated voltage, so
+    /// changing it afterward can degrade closed loop behaviour, though it's the
+    /// right knob for scaling an open loop ERM.
+    pub async fn set_rated_voltage(&mut self, value: u8) -> Result<(), DrvError<E>> {
+        self.write(RatedVoltageReg(value)).await?;
+        self.strength_baseline = None;
+        Ok(())
+    }
+
+    /// Set the OD_CLAMP register, the peak drive during overdrive and in open
+    /// loop. See the datasheet for the formulas, which differ between ERM and
+    /// LRA motors. Like `set_rated_voltage` this can degrade closed loop
+    /// behaviour after calibration.
+    pub async fn set_overdrive_clamp(&mut self, value: u8) -> Result<(), DrvError<E>> {
+        self.write(OverdriveClampReg(value)).await?;
+        self.strength_baseline = None;
+        Ok(())
+    }
+
+    /// Get the supply voltage at the VDD pin in millivolts. The device only
+    /// updates this reading while actively driving a waveform.
+    pub async fn supply_voltage_mv(&mut self) -> Result<u16, DrvError<E>> {
+        let vbat: VbatReg = self.read().await?;
+        Ok((u32::from(vbat.value()) * 5600 / 255) as u16)
+    }
+
+    /// Drive the motor continuously at `duty` using real time playback, which
+    /// is useful as a known strength test tone when validating wiring. The
+    /// device is woken from standby and the duty is held until `stop` is
+    /// called. If `timeout_ms` is given the motor is instead stopped after that
+    /// many milliseconds as a safety net.
+    #[cfg(feature = "rtp")]
+    pub async fn drive_constant(
+        &mut self,
+        duty: u8,
+        timeout_ms: Option<u32>,
+    ) -> Result<(), DrvError<E>> {
+        self.set_mode(Mode::RealTimePlayback).await?;
+        self.set_rtp(duty).await?;
+        self.set_standby(false).await?;
+
+        if let Some(ms) = timeout_ms {
+            self.delay.delay_ms(ms).await;
+            self.stop(BrakeMode::Active).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Stream `samples` as rtp duty cycles, one every `sample_period_us`
+    /// microseconds. The device is put in `Mode::RealTimePlayback` and woken
+    /// from standby. `abort` is checked between samples so long buffers can be
+    /// cut short, and either way the duty is set back to zero at the end.
+    #[cfg(feature = "rtp")]
+    pub async fn play_rtp_buffer(
+        &mut self,
+        samples: &[u8],
+        sample_period_us: u32,
+        abort: Option<&AtomicBool>,
+    ) -> Result<(), DrvError<E>> {
+        self.set_mode(Mode::RealTimePlayback).await?;
+        self.set_standby(false).await?;
+
+        for &duty in samples {
+            if aborted(abort) {
+                break;
+            }
+            self.set_rtp(duty).await?;
+            self.delay.delay_us(sample_period_us).await;
+        }
+
+        self.set_rtp(0).await
+    }
+
+    /// Play an envelope table stored in flash, streaming its samples as rtp
+    /// duty cycles at `sample_rate_hz` through `play_rtp_buffer`. An empty
+    /// envelope does nothing, not even a mode change, and a zero sample rate
+    /// returns `DrvError::InvalidParameter`.
+    #[cfg(feature = "rtp")]
+    pub async fn play_envelope(
+        &mut self,
+        envelope: &'static [u8],
+        sample_rate_hz: u16,
+        abort: Option<&AtomicBool>,
+    ) -> Result<(), DrvError<E>> {
+        if sample_rate_hz == 0 {
+            return Err(DrvError::InvalidParameter);
+        }
+        if envelope.is_empty() {
+            return Ok(());
+        }
+
+        let sample_period_us = 1_000_000 / u32::from(sample_rate_hz);
+        self.play_rtp_buffer(envelope, sample_period_us, abort)
+            .await
+    }
+
+    /// Linearly ramp the rtp duty cycle from `from` to `to` in `steps` steps of
+    /// `step_period_us` microseconds each, leaving the duty at `to`. The device
+    /// is put in `Mode::RealTimePlayback` and woken from standby. `abort` is
+    /// checked between steps and if set the duty is set to zero and the ramp
+    /// ends early.
+    #[cfg(feature = "rtp")]
+    pub async fn rtp_ramp(
+        &mut self,
+        from: u8,
+        to: u8,
+        steps: u16,
+        step_period_us: u32,
+        abort: Option<&AtomicBool>,
+    ) -> Result<(), DrvError<E>> {
+        self.set_mode(Mode::RealTimePlayback).await?;
+        self.set_standby(false).await?;
+
+        let span = i32::from(to) - i32::from(from);
+        for step in 0..=steps {
+            if aborted(abort) {
+                return self.set_rtp(0).await;
+            }
+            let duty = match steps {
+                0 => to,
+                _ => (i32::from(from) + span * i32::from(step) / i32::from(steps)) as u8,
+            };
+            self.set_rtp(duty).await?;
+            self.delay.delay_us(step_period_us).await;
+        }
+
+        Ok(())
+    }
+
+    /// Bench helper for catching swapped motor leads during bring up. Drives
+    /// the motor open loop with a slow ramp in the positive direction using
+    /// bidirectional rtp, where 0x80 is no drive and 0xFF full positive drive,
+    /// so the direction of travel can be observed. Afterward the mode and
+    /// control registers are restored, even if the ramp fails, and `confirm` is
+    /// called if given. If it returns false, meaning the motor moved the wrong
+    /// way, `DrvError::WiringTestFailed` is returned.
+    #[cfg(feature = "rtp")]
+    pub async fn wiring_test(
+        &mut self,
+        confirm: Option<&mut dyn FnMut() -> bool>,
+    ) -> Result<(), DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        let ctrl2: Control2Reg = self.read().await?;
+        let ctrl3: Control3Reg = self.read().await?;
+
+        let ramped = self.wiring_ramp(&ctrl2, &ctrl3).await;
+
+        let restored = async {
+            self.write(ctrl2).await?;
+            self.write(ctrl3).await?;
+            self.write(mode).await
+        }
+        .await;
+        ramped.and(restored)?;
+
+        match confirm.map(|confirm| confirm()) {
+            Some(false) => Err(DrvError::WiringTestFailed),
+            _ => Ok(()),
+        }
+    }
+
+    /// Confirm the motor actually moves with the loaded calibration by driving
+    /// a short closed loop rtp burst. The feedback controller times out when
+    /// it sees no back-EMF, and for LRAs the resonance period it locked onto
+    /// must also be plausible, otherwise `DrvError::NoMotorResponse` is
+    /// returned. The mode and control registers are restored afterward.
+    #[cfg(feature = "rtp")]
+    pub async fn self_test_vibration(&mut self) -> Result<(), DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        let ctrl3: Control3Reg = self.read().await?;
+        // clear any stale latched flags before the burst
+        self.status().await?;
+
+        let responded = self.self_test_burst().await;
+
+        let restored = async {
+            self.write(ctrl3).await?;
+            self.write(mode).await
+        }
+        .await;
+
+        let responded = responded?;
+        restored?;
+
+        if !responded {
+            return Err(DrvError::NoMotorResponse);
+        }
+        Ok(())
+    }
+
+    /// The drive for `self_test_vibration`, returning whether the motor
+    /// responded
+    #[cfg(feature = "rtp")]
+    async fn self_test_burst(&mut self) -> Result<bool, DrvError<E>> {
+        self.set_mode(Mode::RealTimePlayback).await?;
+        self.set_rtp(SELF_TEST_DUTY).await?;
+        self.set_standby(false).await?;
+        self.delay.delay_ms(SELF_TEST_MS).await;
+
+        // the period is only measured while driving
+        let period: LraPeriodReg = self.read().await?;
+        self.set_rtp(0).await?;
+        let status = StatusReg(self.status().await?);
+
+        let resonating = !self.lra || LRA_PERIOD_PLAUSIBLE.contains(&period.value());
+        Ok(resonating && !status.feedback_controller_timed_out())
+    }
+
+    /// The open loop ramp for `wiring_test`
+    #[cfg(feature = "rtp")]
+    async fn wiring_ramp(
+        &mut self,
+        ctrl2: &Control2Reg,
+        ctrl3: &Control3Reg,
+    ) -> Result<(), DrvError<E>> {
+        let mut bidir = Control2Reg(ctrl2.value());
+        bidir.set_bidir_input(true);
+        self.write(bidir).await?;
+
+        let mut open_loop = Control3Reg(ctrl3.value());
+        if self.lra {
+            open_loop.set_lra_open_loop(true);
+        } else {
+            open_loop.set_erm_open_loop(true);
+        }
+        open_loop.set_data_format_rtp(true);
+        self.write(open_loop).await?;
+
+        // preload the zero point before entering rtp mode
+        self.write(RealTimePlaybackInputReg(WIRING_TEST_ZERO))
+            .await?;
+        let mut mode: ModeReg = self.read().await?;
+        mode.set_standby(false);
+        mode.set_mode(registers::Mode::RealTimePlayback as u8);
+        self.write(mode).await?;
+
+        for duty in WIRING_TEST_ZERO..=u8::MAX {
+            self.set_rtp(duty).await?;
+            self.delay.delay_ms(WIRING_TEST_STEP_MS).await;
+        }
+
+        self.set_rtp(WIRING_TEST_ZERO).await
+    }
+
+    /// Stop whatever is playing. Clears the GO bit, which cancels any rom
+    /// sequence, and then brings the motor to rest according to `brake`. Call
+    /// this when cancelling playback as the driver can't stop the motor on drop.
+    pub async fn stop(&mut self, brake: BrakeMode) -> Result<(), DrvError<E>> {
+        let mut go: GoReg = self.read().await?;
+        go.set_go(false);
+        self.write(go).await?;
+
+        match brake {
+            BrakeMode::Coast => self.set_standby(true).await,
+            BrakeMode::Active => self.brake().await,
+        }
+    }
+
+    /// Play a single `Effect` and wait for it to finish as decided by
+    /// `completion`. The device should already be in `Mode::Rom` and out of
+    /// standby.
+    #[cfg(feature = "rom")]
+    pub async fn play_effect(
+        &mut self,
+        effect: Effect,
+        completion: CompletionStrategy,
+    ) -> Result<(), DrvError<E>> {
+        self.set_rom_single(effect).await?;
+        self.set_go().await?;
+        self.wait_for_completion(effect, completion, None).await?;
+        self.auto_brake().await
+    }
+
+    /// Play a pattern of any length and wait for it to finish. Each run of 8
+    /// slots is loaded and started with `play_sequence_now`, so longer
+    /// patterns are played as back to back sequences. The device should
+    /// already be in `Mode::Rom` and out of standby.
+    #[cfg(feature = "rom")]
+    pub async fn play_pattern(&mut self, slots: &[Effect]) -> Result<(), DrvError<E>> {
+        for chunk in slots.chunks(8) {
+            self.play_sequence_now(chunk).await?;
+            self.wait_for_go(None).await?;
+        }
+
+        self.auto_brake().await
+    }
+
+    /// Look up `name` in `patterns` and play it with `play_pattern`. Returns
+    /// `DrvError::InvalidParameter` if there is no pattern by that name.
+    #[cfg(feature = "rom")]
+    pub async fn play_named(
+        &mut self,
+        patterns: &[NamedPattern],
+        name: &str,
+    ) -> Result<(), DrvError<E>> {
+        let pattern = NamedPattern::find(patterns, name).ok_or(DrvError::InvalidParameter)?;
+        self.play_pattern(pattern.slots).await
+    }
+
+    /// Play a sequence of effects drawn from different libraries, one effect
+    /// per GO since each GO plays from a single library. The library is only
+    /// switched when it differs from the previous item, and each effect is
+    /// waited on before the next. The device should already be in `Mode::Rom`
+    /// and out of standby.
+    #[cfg(feature = "rom")]
+    pub async fn play_mixed(
+        &mut self,
+        items: &[(Library, Effect)],
+        completion: CompletionStrategy,
+    ) -> Result<(), DrvError<E>> {
+        let mut current = None;
+        for &(library, effect) in items {
+            if current != Some(library) {
+                self.set_library(library).await?;
+                current = Some(library);
+            }
+            self.play_effect(effect, completion).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Play a single `Effect` `count` times, waiting for each to finish and
+    /// then `gap_ms` milliseconds before the next. `abort` is checked on either
+    /// side of each gap and while waiting for playback, cancelling the effect
+    /// if set. The device should already be in `Mode::Rom` and out of standby.
+    #[cfg(feature = "rom")]
+    pub async fn play_effect_repeated(
+        &mut self,
+        effect: Effect,
+        count: u16,
+        gap_ms: u32,
+        completion: CompletionStrategy,
+        abort: Option<&AtomicBool>,
+    ) -> Result<(), DrvError<E>> {
+        self.set_rom_single(effect).await?;
+
+        for repeat in 0..count {
+            if aborted(abort) {
+                break;
+            }
+            if repeat > 0 {
+                self.delay.delay_ms(gap_ms).await;
+                // the abort may have come in during the gap
+                if aborted(abort) {
+                    break;
+                }
+            }
+            self.set_go().await?;
+            self.wait_for_completion(effect, completion, abort).await?;
+            self.auto_brake().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Play `sequence` from where it last left off until it finishes or is
+    /// paused with `RomSequence::pause`. Pausing mid slot cancels the playing
+    /// effect and the sequence later resumes from the following slot. Returns
+    /// true once the sequence has finished. The device should already be in
+    /// `Mode::Rom` and out of standby.
+    #[cfg(feature = "rom")]
+    pub async fn play_sequence(&mut self, sequence: &RomSequence) -> Result<bool, DrvError<E>> {
+        while let Some(effect) = sequence.next_slot() {
+            if sequence.is_paused() {
+                return Ok(false);
+            }
+            self.set_rom_single(effect).await?;
+            self.set_go().await?;
+            sequence.advance();
+            self.wait_for_go(Some(&sequence.paused)).await?;
+        }
+
+        self.auto_brake().await?;
+        Ok(true)
+    }
+
+    /// Unpause `sequence` and continue playing it with `play_sequence`
+    #[cfg(feature = "rom")]
+    pub async fn resume(&mut self, sequence: &RomSequence) -> Result<bool, DrvError<E>> {
+        sequence.paused.store(false, Ordering::Relaxed);
+        self.play_sequence(sequence).await
+    }
+
+    /// Play a single `Effect` louder than the configured baseline by scaling
+    /// the overdrive clamp by `boost_ratio` for its duration. The previous clamp
+    /// is restored afterward, even if playback fails.
+    #[cfg(feature = "rom")]
+    pub async fn play_effect_boosted(
+        &mut self,
+        effect: Effect,
+        boost_ratio: f32,
+        completion: CompletionStrategy,
+    ) -> Result<(), DrvError<E>> {
+        if boost_ratio.is_nan() || boost_ratio < 0.0 {
+            return Err(DrvError::InvalidParameter);
+        }
+
+        let clamp: OverdriveClampReg = self.read().await?;
+        // float to int casts saturate so this tops out at the max clamp
+        let boosted = OverdriveClampReg((clamp.value() as f32 * boost_ratio) as u8);
+        self.write(boosted).await?;
+
+        let played = self.play_effect(effect, completion).await;
+        let restored = self.write(clamp).await;
+        played.and(restored)
+    }
+
+    /// Trigger a GO for whatever mode is enabled. The GO bit can read back set
+    /// for a moment after this even if playback finishes immediately, see
+    /// `set_go_confirmed` if the bit is checked right away.
+    pub async fn set_go(&mut self) -> Result<(), DrvError<E>> {
+        self.debug_assert_mode(&GO_MODES, "set_go").await?;
         let mut go: GoReg = self.read().await?;
 
         go.set_go(true);
         self.write(go).await
     }
 
+    /// Trigger a GO and wait out the moment where the GO bit reads back set
+    /// regardless of playback, so a completion check straight after isn't
+    /// fooled. Returns whether playback is still running after that.
+    pub async fn set_go_confirmed(&mut self) -> Result<bool, DrvError<E>> {
+        self.set_go().await?;
+        self.delay.delay_us(GO_SETTLE_US).await;
+        self.go().await
+    }
+
+    /// Trigger a GO and estimate when the loaded rom sequence will finish so
+    /// a follow up can be scheduled without polling. The sequence and the
+    /// playback interval are read back and the effect durations summed up to
+    /// the first `Effect::Stop`. Based on `Effect::estimated_duration_ms`, so
+    /// it errs long.
+    #[cfg(feature = "rom")]
+    pub async fn set_go_estimated(&mut self) -> Result<EstimatedDuration, DrvError<E>> {
+        let mut slots = [0u8; 8];
+        self.read_burst(Waveform0Reg::ADDRESS, &mut slots).await?;
+        let ctrl5: Control5Reg = self.read().await?;
+
+        let ms = slots
+            .iter()
+            .map_while(|&id| Effect::from_id(id))
+            .take_while(|effect| *effect != Effect::Stop)
+            .map(|effect| match effect {
+                // waits aren't affected by the playback interval
+                Effect::Delays(_) => effect.estimated_duration_ms(),
+                _ if ctrl5.playback_interval() => effect.estimated_duration_ms() / 5,
+                _ => effect.estimated_duration_ms(),
+            })
+            .sum();
+
+        self.set_go().await?;
+        Ok(EstimatedDuration { ms })
+    }
+
     /// Get the go bit. For some modes the go bit can be polled to see when it
     /// clears indicating a waveform has completed playback.
-    pub async fn go(&mut self) -> Result<bool, DrvError> {
+    pub async fn go(&mut self) -> Result<bool, DrvError<E>> {
         Ok(self.read::<GoReg>().await?.go())
     }
 
+    /// Wait for the GO bit to clear, such as when a rom sequence finishes
+    /// playing. Returns `DrvError::Timeout` if it's still set after the
+    /// longest sequence the waveform sequencer can hold.
+    pub async fn wait_until_idle(&mut self) -> Result<(), DrvError<E>> {
+        self.wait_for_idle(IDLE_TIMEOUT_MS).await
+    }
+
+    /// Whether the motor is currently being driven, whichever mode is driving
+    /// it. Always false in standby. Triggered modes are active while the GO
+    /// bit is set and rtp is active for any non zero duty cycle. The device
+    /// can't report the level on the IN/TRIG pin, so pwm, analog and audio to
+    /// vibe are considered active whenever they are out of standby.
+    pub async fn is_output_active(&mut self) -> Result<bool, DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        if mode.standby() {
+            return Ok(false);
+        }
+
+        match mode.mode() {
+            registers::Mode::RealTimePlayback => {
+                Ok(self.read::<RealTimePlaybackInputReg>().await?.value() != 0)
+            }
+            registers::Mode::PwmInputAndAnalogInput | registers::Mode::AudioToVibe => Ok(true),
+            _ => self.go().await,
+        }
+    }
+
     /// Enabling standby goes into a low power state but maintains all mode
     /// configuration
-    pub async fn set_standby(&mut self, enable: bool) -> Result<(), DrvError> {
+    pub async fn set_standby(&mut self, enable: bool) -> Result<(), DrvError<E>> {
         let mut mode: ModeReg = self.read().await?;
         mode.set_standby(enable);
         self.write(mode).await
     }
 
     /// Get the status bits
-    pub async fn status(&mut self) -> Result<u8, DrvError> {
+    pub async fn status(&mut self) -> Result<u8, DrvError<E>> {
         let status: StatusReg = self.read().await?;
         Ok(status.value())
     }
 
+    /// Get the status bits decoded into a `Status`. The fault and result
+    /// flags clear when read, so each is only reported once.
+    pub async fn read_status(&mut self) -> Result<Status, DrvError<E>> {
+        let status: StatusReg = self.read().await?;
+        Ok(status.into())
+    }
+
     /// Get the LoadParams that were loaded at startup or calculated via
     /// Calibration
-    pub async fn calibration(&mut self) -> Result<LoadParams, DrvError> {
+    pub async fn calibration(&mut self) -> Result<LoadParams, DrvError<E>> {
         let feedback: FeedbackControlReg = self.read().await?;
 
         let compenstation: AutoCalibrationCompensationReg = self.read().await?;
@@ -250,131 +1088,1046 @@ where
         })
     }
 
+    /// A quick health check for board bring up: is this a DRV2605L, are any
+    /// faults latched, do the calibration results look plausible and is the
+    /// OTP programmed. Nothing is written, though reading the status clears
+    /// its latched fault flags. Only two transactions are used.
+    pub async fn probe(&mut self) -> Result<ProbeResult, DrvError<E>> {
+        let status = StatusReg(self.status().await?);
+
+        // compensation through control 4 in a single read
+        let mut buf = [0u8; 7];
+        self.read_burst(AutoCalibrationCompensationReg::ADDRESS, &mut buf)
+            .await?;
+        let compensation = buf[0];
+        let back_emf = buf[1];
+        let feedback = FeedbackControlReg(buf[2]);
+        let ctrl4 = Control4Reg(buf[6]);
+        // still at the power on values, calibration never ran or was loaded
+        let untouched = compensation == AutoCalibrationCompensationReg::default().value()
+            && back_emf == AutoCalibrationCompensationBackEmfReg::default().value();
+
+        Ok(ProbeResult {
+            device_id: status.device_id(),
+            device_id_ok: status.device_id() == 7,
+            motor_type_ok: feedback.n_erm_lra() == self.lra,
+            over_current: status.oc_detected(),
+            over_temp: status.over_temp(),
+            calibration_plausible: !untouched
+                && CALIBRATION_RESULT_PLAUSIBLE.contains(&compensation)
+                && CALIBRATION_RESULT_PLAUSIBLE.contains(&back_emf),
+            otp_programmed: ctrl4.otp_status(),
+        })
+    }
+
+    /// Check the bus carries register contents faithfully, catching I2C
+    /// implementations that mishandle clock stretching and corrupt reads.
+    /// Alternating bit patterns are written to the last waveform sequencer
+    /// slot and each read back several times, any mismatch returning
+    /// `DrvError::BusUnreliable`. The slot is restored afterward. Best run
+    /// right after construction, before anything is queued for playback.
+    pub async fn check_bus_health(&mut self) -> Result<(), DrvError<E>> {
+        let mut original = [0u8; 1];
+        self.read_burst(BUS_CHECK_ADDRESS, &mut original).await?;
+
+        let consistent = self.bus_check_patterns().await;
+        self.write_raw(BUS_CHECK_ADDRESS, original[0]).await?;
+
+        if !consistent? {
+            return Err(DrvError::BusUnreliable);
+        }
+        Ok(())
+    }
+
+    /// Gather the motor type, calibration, voltage and feedback configuration,
+    /// current mode and fault flags into one `DeviceReport`, handy for dumping
+    /// when chasing a haptics bug. Reading the status clears its latched
+    /// fault flags.
+    pub async fn full_report(&mut self) -> Result<DeviceReport, DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        let status: StatusReg = self.read().await?;
+        let rated: RatedVoltageReg = self.read().await?;
+        let clamp: OverdriveClampReg = self.read().await?;
+        let feedback: FeedbackControlReg = self.read().await?;
+        let calibration = self.calibration().await?;
+
+        Ok(DeviceReport {
+            lra: self.lra,
+            calibration_source: self.calibration_source,
+            calibration,
+            rated_voltage: rated.value(),
+            overdrive_clamp: clamp.value(),
+            brake_factor: feedback.fb_brake_factor(),
+            loop_gain: feedback.loop_gain(),
+            mode: mode.mode(),
+            standby: mode.standby(),
+            over_current: status.oc_detected(),
+            over_temp: status.over_temp(),
+            feedback_timed_out: status.feedback_controller_timed_out(),
+        })
+    }
+
+    /// Identify the part from the status register. Note reading the status
+    /// clears its latched fault flags.
+    pub async fn device_info(&mut self) -> Result<DeviceInfo, DrvError<E>> {
+        let status = self.status().await?;
+        Ok(DeviceInfo {
+            id: StatusReg(status).device_id(),
+            status,
+        })
+    }
+
+    /// Permanently burn the rated voltage, overdrive clamp, calibration
+    /// results and feedback control registers (0x16 through 0x1A) into the
+    /// one time programmable memory, so later boots can use
+    /// `Calibration::Otp`.
+    ///
+    /// **This can only ever be done once per device and can't be undone.**
+    /// Make sure the registers hold the final calibration first, and note the
+    /// datasheet requires VDD at 4.2V ± 5% while programming. Returns
+    /// `DrvError::OTPAlreadyProgrammed` without writing anything if the OTP
+    /// was already programmed, and `DrvError::OTPProgramFailed` if the device
+    /// doesn't report it programmed afterward.
+    pub async fn program_otp(&mut self) -> Result<(), DrvError<E>> {
+        if self.is_otp().await? {
+            return Err(DrvError::OTPAlreadyProgrammed);
+        }
+
+        let mut ctrl4: Control4Reg = self.read().await?;
+        ctrl4.set_otp_program(true);
+        self.write(ctrl4).await?;
+
+        for _ in 0..OTP_PROGRAM_POLL_ATTEMPTS {
+            self.delay.delay_ms(OTP_PROGRAM_POLL_INTERVAL_MS).await;
+            if self.is_otp().await? {
+                return Ok(());
+            }
+        }
+
+        Err(DrvError::OTPProgramFailed)
+    }
+
+    /// Re-run auto calibration with `params`, as `new` does for
+    /// `Calibration::Auto`, and return the fresh `LoadParams` to cache for
+    /// `Calibration::Load`. Useful as motors age or resonance shifts with
+    /// temperature. The feedback and control registers are rewritten from
+    /// `params`, and the device is left in standby. Returns
+    /// `DrvError::CalibrationFailed` if calibration doesn't converge.
+    pub async fn recalibrate(
+        &mut self,
+        params: CalibrationParams,
+    ) -> Result<LoadParams, DrvError<E>> {
+        if !params.is_valid() {
+            return Err(DrvError::InvalidParameter);
+        }
+
+        self.write_batch(&params.to_register_image(self.lra))
+            .await?;
+        // the rated voltage was just rewritten
+        self.strength_baseline = None;
+
+        let load = self.calibrate().await?;
+        self.calibration_source = CalibrationSource::Auto;
+        self.set_standby(true).await?;
+
+        Ok(load)
+    }
+
+    /// Confirm the calibration registers hold exactly `expected`, such as
+    /// after `Calibration::Load` on a flaky bus, returning
+    /// `DrvError::CalibrationMismatch` if any field differs. Nothing is
+    /// rewritten, see `ensure_calibrated` for that.
+    pub async fn verify_calibration(&mut self, expected: &LoadParams) -> Result<(), DrvError<E>> {
+        if self.calibration().await? != *expected {
+            return Err(DrvError::CalibrationMismatch);
+        }
+        Ok(())
+    }
+
+    /// Check the live calibration still matches `expected`, such as after a
+    /// brownout, and repair it if not. Compensation and back-EMF may differ by
+    /// `CALIBRATION_TOLERANCE` while the BEMF_GAIN must match exactly. Drifted
+    /// values are rewritten and read back after a moment, returning
+    /// `DrvError::CalibrationMismatch` only if the rewrite didn't stick.
+    pub async fn ensure_calibrated(&mut self, expected: &LoadParams) -> Result<(), DrvError<E>> {
+        if self.calibration().await?.matches(expected) {
+            return Ok(());
+        }
+
+        self.set_calibration(*expected).await?;
+        self.delay.delay_us(CALIBRATION_SETTLE_US).await;
+
+        if !self.calibration().await?.matches(expected) {
+            return Err(DrvError::CalibrationMismatch);
+        }
+        Ok(())
+    }
+
+    /// Get the calibration registers exactly as read, for cloning calibration
+    /// across units without interpreting it. In order that is the
+    /// compensation, the back-EMF and the whole feedback control byte which
+    /// holds the BEMF_GAIN.
+    pub async fn raw_calibration(&mut self) -> Result<[u8; 3], DrvError<E>> {
+        let compenstation: AutoCalibrationCompensationReg = self.read().await?;
+        let back_emf: AutoCalibrationCompensationBackEmfReg = self.read().await?;
+        let feedback: FeedbackControlReg = self.read().await?;
+
+        Ok([compenstation.value(), back_emf.value(), feedback.value()])
+    }
+
+    /// Write calibration registers previously fetched with `raw_calibration`.
+    /// Note the feedback control byte is written whole, so the motor type, brake
+    /// factor and loop gain come along with the BEMF_GAIN.
+    pub async fn write_raw_calibration(&mut self, raw: [u8; 3]) -> Result<(), DrvError<E>> {
+        self.write(AutoCalibrationCompensationReg(raw[0])).await?;
+        self.write(AutoCalibrationCompensationBackEmfReg(raw[1]))
+            .await?;
+        self.write(FeedbackControlReg(raw[2])).await
+    }
+
+    /// Set the feedback brake factor, 0: 1x, 1: 2x, 2: 3x, 3: 4x, 4: 6x,
+    /// 5: 8x, 6: 16x, 7: braking disabled. Takes effect live in any mode
+    /// without recalibrating. Returns `DrvError::InvalidParameter` above 7.
+    pub async fn set_brake_factor(&mut self, factor: u8) -> Result<(), DrvError<E>> {
+        if factor > 7 {
+            return Err(DrvError::InvalidParameter);
+        }
+
+        let mut feedback: FeedbackControlReg = self.read().await?;
+        feedback.set_fb_brake_factor(factor);
+        self.write(feedback).await
+    }
+
+    /// Set the feedback loop gain, 0: low, 1: medium, 2: high, 3: very high.
+    /// Takes effect live in any mode without recalibrating. Returns
+    /// `DrvError::InvalidParameter` above 3.
+    pub async fn set_loop_gain(&mut self, gain: u8) -> Result<(), DrvError<E>> {
+        if gain > 3 {
+            return Err(DrvError::InvalidParameter);
+        }
+
+        let mut feedback: FeedbackControlReg = self.read().await?;
+        feedback.set_loop_gain(gain);
+        self.write(feedback).await
+    }
+
+    /// Get the programmed brake factor and loop gain
+    pub async fn feedback_params(&mut self) -> Result<FeedbackParams, DrvError<E>> {
+        let feedback: FeedbackControlReg = self.read().await?;
+        Ok(FeedbackParams {
+            brake_factor: feedback.fb_brake_factor(),
+            loop_gain: feedback.loop_gain(),
+        })
+    }
+
+    /// A single relative figure for the closed loop gain, combining the
+    /// back-EMF amplifier gain with the loop gain. The BEMF_GAIN factor comes
+    /// from the datasheet for the configured motor type and is multiplied by
+    /// the LOOP_GAIN setting counted from 1 (low) to 4 (very high). The
+    /// datasheet gives no absolute loop gain so this is only meaningful for
+    /// comparing configurations, higher being faster but less stable.
+    pub async fn effective_loop_gain(&mut self) -> Result<f32, DrvError<E>> {
+        let feedback: FeedbackControlReg = self.read().await?;
+
+        let bemf_gains = match feedback.n_erm_lra() {
+            true => [5.0, 10.0, 20.0, 30.0],
+            false => [0.33, 1.0, 1.8, 4.0],
+        };
+        let bemf_gain = bemf_gains[usize::from(feedback.bemf_gain())];
+        Ok(bemf_gain * f32::from(feedback.loop_gain() + 1))
+    }
+
+    /// Set the noise gate threshold for PWM and analog inputs, below which the
+    /// input is treated as zero to avoid idle buzzing
+    #[cfg(feature = "analog")]
+    pub async fn set_noise_gate_threshold(
+        &mut self,
+        threshold: NoiseGateThreshold,
+    ) -> Result<(), DrvError<E>> {
+        let mut ctrl3: Control3Reg = self.read().await?;
+        ctrl3.set_ng_thresh(threshold as u8);
+        self.write(ctrl3).await
+    }
+
+    /// Get the noise gate threshold for PWM and analog inputs
+    #[cfg(feature = "analog")]
+    pub async fn noise_gate_threshold(&mut self) -> Result<NoiseGateThreshold, DrvError<E>> {
+        let ctrl3: Control3Reg = self.read().await?;
+        Ok(ctrl3.ng_thresh().into())
+    }
+
+    /// Set how many failed attempts at auto-resonance lock are made before an
+    /// LRA drops to open loop, when automatic open loop transition is enabled
+    pub async fn set_auto_open_loop_attempts(
+        &mut self,
+        attempts: AutoOlCnt,
+    ) -> Result<(), DrvError<E>> {
+        let mut ctrl5: Control5Reg = self.read().await?;
+        ctrl5.set_auto_ol_cnt(attempts as u8);
+        self.write(ctrl5).await
+    }
+
+    /// Get how many failed auto-resonance attempts are made before an LRA
+    /// drops to open loop. Note AUTO_OL_CNT lives in Control5.
+    pub async fn auto_open_loop_attempts(&mut self) -> Result<AutoOlCnt, DrvError<E>> {
+        let ctrl5: Control5Reg = self.read().await?;
+        Ok(ctrl5.auto_ol_cnt().into())
+    }
+
+    /// Get the audio-to-vibe configuration registers along with the AC
+    /// coupling bit, to confirm what is actually programmed while tuning
+    #[cfg(feature = "audio")]
+    pub async fn atv_config(&mut self) -> Result<AtvConfig, DrvError<E>> {
+        let ctrl: AudioToVibeControlReg = self.read().await?;
+        let min_input: AudioToVibeMinInputReg = self.read().await?;
+        let max_input: AudioToVibeMaxInputReg = self.read().await?;
+        let min_drive: AudioToVibeMinDriveReg = self.read().await?;
+        let max_drive: AudioToVibeMaxDriveReg = self.read().await?;
+        let ctrl1: Control1Reg = self.read().await?;
+
+        Ok(AtvConfig {
+            peak_time: ctrl.ath_peak_time(),
+            filter: ctrl.ath_filter(),
+            min_input: min_input.value(),
+            max_input: max_input.value(),
+            min_drive: min_drive.value(),
+            max_drive: max_drive.value(),
+            ac_couple: ctrl1.ac_couple(),
+        })
+    }
+
+    /// Whether auto calibration completed in closed loop. An LRA that loses
+    /// resonance lock during calibration falls back to open loop, usually a
+    /// sign of poor mounting. `None` unless the device was auto calibrated.
+    pub fn calibrated_closed_loop(&self) -> Option<bool> {
+        self.calibrated_closed_loop
+    }
+
+    /// Get which kind of `Calibration` the device was constructed with
+    pub fn calibration_source(&self) -> CalibrationSource {
+        self.calibration_source
+    }
+
+    /// Capture the mode, library, voltage, calibration, feedback and the five
+    /// control registers so they can be put back with `restore_config` after
+    /// power gating the device, instead of running through `new` again. The
+    /// voltage through control registers are read in a single transaction.
+    pub async fn snapshot_config(&mut self) -> Result<ConfigSnapshot, DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        let library: LibrarySelectionReg = self.read().await?;
+
+        let mut buf = [0u8; 10];
+        self.read_burst(RatedVoltageReg::ADDRESS, &mut buf).await?;
+
+        Ok(ConfigSnapshot {
+            mode: mode.value(),
+            library: library.value(),
+            rated_voltage: buf[0],
+            overdrive_clamp: buf[1],
+            compensation: buf[2],
+            back_emf: buf[3],
+            feedback: buf[4],
+            control: [buf[5], buf[6], buf[7], buf[8], buf[9]],
+        })
+    }
+
+    /// Write back a `ConfigSnapshot` taken with `snapshot_config`. The voltage
+    /// through control registers go out in a single transaction, followed by
+    /// the library and finally the mode so the device only leaves standby once
+    /// it's fully configured. A device reset or otp programming captured in the
+    /// snapshot is never replayed.
+    pub async fn restore_config(&mut self, snapshot: &ConfigSnapshot) -> Result<(), DrvError<E>> {
+        let [ctrl1, ctrl2, ctrl3, ctrl4, ctrl5] = snapshot.control;
+        // never restore a pending otp programming, it can only happen once
+        let mut ctrl4 = Control4Reg(ctrl4);
+        ctrl4.set_otp_program(false);
+        let buf: [u8; 11] = [
+            RatedVoltageReg::ADDRESS,
+            snapshot.rated_voltage,
+            snapshot.overdrive_clamp,
+            snapshot.compensation,
+            snapshot.back_emf,
+            snapshot.feedback,
+            ctrl1,
+            ctrl2,
+            ctrl3,
+            ctrl4.value(),
+            ctrl5,
+        ];
+        self.write_burst(&buf).await?;
+        self.strength_baseline = None;
+
+        let library = LibrarySelectionReg(snapshot.library);
+        self.library = Some(library.library_selection());
+        self.write(library).await?;
+
+        // never restore a pending reset
+        let mut mode = ModeReg(snapshot.mode);
+        mode.set_dev_reset(false);
+        self.write(mode).await
+    }
+
+    /// The LRA resonance period the device is tracking, period (us) =
+    /// lra_period * 98.46us. It's only measured while the LRA is driven in
+    /// closed loop, so read it during or just after playback. Returns
+    /// `DrvError::WrongMotorType` for ERM motors.
+    pub async fn lra_period(&mut self) -> Result<u8, DrvError<E>> {
+        if !self.lra {
+            return Err(DrvError::WrongMotorType);
+        }
+
+        let period: LraPeriodReg = self.read().await?;
+        Ok(period.value())
+    }
+
+    /// The LRA resonant frequency in hertz from `lra_period`, or 0 if the
+    /// device hasn't measured a period yet
+    pub async fn lra_resonant_frequency_hz(&mut self) -> Result<f32, DrvError<E>> {
+        let period = self.lra_period().await?;
+        if period == 0 {
+            return Ok(0.0);
+        }
+
+        Ok(1_000_000.0 / (f32::from(period) * LRA_PERIOD_US))
+    }
+
+    /// Capture the LRA resonance period at a known temperature as the baseline
+    /// for `thermal_proxy`. The period is only measured while the LRA is
+    /// driven in closed loop, so call this during or just after playback. The
+    /// baseline is dropped by `reset` and `disable`. Returns
+    /// `DrvError::WrongMotorType` for ERM motors.
+    pub async fn capture_thermal_baseline(&mut self) -> Result<(), DrvError<E>> {
+        if !self.lra {
+            return Err(DrvError::WrongMotorType);
+        }
+
+        let period: LraPeriodReg = self.read().await?;
+        self.thermal_baseline = Some(period.value());
+        Ok(())
+    }
+
+    /// An approximate temperature indicator for early warning before the over
+    /// temperature shutdown trips. The LRA resonance drifts with temperature so
+    /// this reports how far the resonance period has moved, in microseconds,
+    /// from the baseline captured with `capture_thermal_baseline`. Like the
+    /// baseline it should be read during or just after playback. Returns
+    /// `DrvError::ThermalBaselineMissing` if no baseline was captured.
+    pub async fn thermal_proxy(&mut self) -> Result<i16, DrvError<E>> {
+        if !self.lra {
+            return Err(DrvError::WrongMotorType);
+        }
+        let baseline = self
+            .thermal_baseline
+            .ok_or(DrvError::ThermalBaselineMissing)?;
+
+        let period: LraPeriodReg = self.read().await?;
+        let drift = i32::from(period.value()) - i32::from(baseline);
+        // each LRA_PERIOD count is 98.46us
+        Ok((drift * 9846 / 100) as i16)
+    }
+
+    /// Run the actuator diagnostic and return what it measured. The device
+    /// has no impedance register, so alongside the pass/fail result this
+    /// captures the supply and LRA period readings taken while the actuator
+    /// was driven, which can be trended over the life of a product. A failed
+    /// diagnostic is reported through `DiagnosticMeasurement::passed` rather
+    /// than an error so the readings aren't lost. The previous mode and
+    /// standby state are restored afterward.
+    pub async fn diagnostics(&mut self) -> Result<DiagnosticMeasurement, DrvError<E>> {
+        let previous: ModeReg = self.read().await?;
+        let mut mode = ModeReg(previous.value());
+        mode.set_standby(false);
+        mode.set_mode(registers::Mode::Diagnostics as u8);
+        self.write(mode).await?;
+
+        self.set_go().await?;
+        self.wait_for_idle(SELF_TIMED_GO_TIMEOUT_MS).await?;
+
+        let reg = StatusReg(self.status().await?);
+        let vbat: VbatReg = self.read().await?;
+        let period: LraPeriodReg = self.read().await?;
+        self.write(previous).await?;
+
+        Ok(DiagnosticMeasurement {
+            passed: !reg.diagnostic_result(),
+            over_current: reg.oc_detected(),
+            over_temp: reg.over_temp(),
+            feedback_timed_out: reg.feedback_controller_timed_out(),
+            vbat: vbat.value(),
+            lra_period: period.value(),
+        })
+    }
+
     /* Private calls */
 
+    /// Shared by the constructors that calibrate
+    async fn init(
+        i2c: I2C,
+        delay: D,
+        en: Option<EN>,
+        address: u8,
+        calibration: Calibration,
+        lra: bool,
+        inter_op_delay_us: u32,
+    ) -> Result<Self, DrvError<E>> {
+        let mut haptic = Self {
+            i2c,
+            delay,
+            en,
+            address,
+            lra,
+            calibration_source: calibration.source(),
+            thermal_baseline: None,
+            library: None,
+            strict: false,
+            auto_brake: false,
+            strength_baseline: None,
+            calibrated_closed_loop: None,
+            inter_op_delay_us,
+        };
+        if haptic.en.is_some() {
+            haptic.enable().await?;
+        }
+        haptic.check_id(7).await?;
+        haptic.reset().await?;
+
+        match calibration {
+            // device will get c/alibration values out of the otp if the otp bit is set
+            Calibration::Otp => {
+                if !haptic.is_otp().await? {
+                    return Err(DrvError::OTPNotProgrammed);
+                }
+            }
+            // load up previously calibrated values
+            Calibration::Load(c) => haptic.set_calibration(c).await?,
+            Calibration::Auto(c) => {
+                haptic.write_batch(&c.to_register_image(lra)).await?;
+                haptic.calibrate().await?;
+            }
+        }
+
+        haptic.set_standby(true).await?;
+
+        Ok(haptic)
+    }
+
+    /// In debug builds assert the device is in one of the `allowed` modes
+    /// before `operation`, catching mode mix ups during development. The mode
+    /// read and the check compile away entirely in release builds.
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    async fn debug_assert_mode(
+        &mut self,
+        allowed: &[registers::Mode],
+        operation: &str,
+    ) -> Result<(), DrvError<E>> {
+        #[cfg(debug_assertions)]
+        {
+            let mode = self.read::<ModeReg>().await?.mode();
+            debug_assert!(
+                allowed.contains(&mode),
+                "{} while in {:?} mode",
+                operation,
+                mode
+            );
+        }
+        Ok(())
+    }
+
+    /// Drop everything tracked about the device's registers and measurements
+    /// once they're lost to a reset or power down
+    fn forget_device_state(&mut self) {
+        self.library = None;
+        self.auto_brake = false;
+        self.strength_baseline = None;
+        self.calibrated_closed_loop = None;
+        self.thermal_baseline = None;
+    }
+
+    /// In strict mode reject operations when the device isn't in one of the
+    /// `allowed` modes they need
+    async fn check_mode(&mut self, allowed: &[registers::Mode]) -> Result<(), DrvError<E>> {
+        if !self.strict {
+            return Ok(());
+        }
+
+        let mode: ModeReg = self.read().await?;
+        if !allowed.contains(&mode.mode()) {
+            return Err(DrvError::WrongMode);
+        }
+        Ok(())
+    }
+
+    /// Check `slots` against strict mode and the current mode, then load them
+    /// into the waveform sequencer as described by `plan::waveforms`
+    #[cfg(feature = "rom")]
+    async fn load_waveforms(
+        &mut self,
+        slots: &[Effect],
+        go: bool,
+        operation: &str,
+    ) -> Result<(), DrvError<E>> {
+        let writes = plan::waveforms(slots, go)?;
+        plan::check_effects(self.strict, self.library, slots)?;
+        self.check_mode(&TRIGGERED_MODES).await?;
+        self.debug_assert_mode(&TRIGGERED_MODES, operation).await?;
+        self.write_batch(writes.as_slice()).await
+    }
+
     /// Write `value` to `register`
-    async fn write<REG>(&mut self, register: REG) -> Result<(), DrvError>
+    async fn write<REG>(&mut self, register: REG) -> Result<(), DrvError<E>>
     where
         REG: Register,
     {
+        self.write_raw(REG::ADDRESS, register.value()).await
+    }
+
+    /// Wait out the inter operation delay, if any, ahead of a transaction
+    async fn pace(&mut self) {
+        if self.inter_op_delay_us > 0 {
+            self.delay.delay_us(self.inter_op_delay_us).await;
+        }
+    }
+
+    /// Write `value` to the register at `address`
+    async fn write_raw(&mut self, address: u8, value: u8) -> Result<(), DrvError<E>> {
+        self.pace().await;
         self.i2c
-            .write(ADDRESS, &[REG::ADDRESS, register.value()])
+            .write(self.address, &[address, value])
             .await
-            .map_err(|_| DrvError::ConnectionError)
+            .map_err(DrvError::I2c)
     }
 
     /// Read the register
-    async fn read<REG>(&mut self) -> Result<REG, DrvError>
+    async fn read<REG>(&mut self) -> Result<REG, DrvError<E>>
     where
         REG: Register + From<u8>,
     {
         let mut buf = [0u8; 1];
+        self.pace().await;
         self.i2c
-            .write_read(ADDRESS, &[REG::ADDRESS], &mut buf)
+            .write_read(self.address, &[REG::ADDRESS], &mut buf)
             .await
-            .map_err(|_| DrvError::ConnectionError)?;
+            .map_err(DrvError::I2c)?;
         Ok(buf[0].into())
     }
 
-    async fn check_id(&mut self, id: u8) -> Result<(), DrvError> {
-        let reg = StatusReg(self.status().await?);
-        if reg.device_id() != id {
+    /// Write `buf`, an address followed by values for it and the registers
+    /// after it, in one transaction
+    async fn write_burst(&mut self, buf: &[u8]) -> Result<(), DrvError<E>> {
+        self.pace().await;
+        self.i2c
+            .write(self.address, buf)
+            .await
+            .map_err(DrvError::I2c)
+    }
+
+    /// Write (address, value) pairs in order, coalescing each run of
+    /// consecutive addresses into a single burst transaction
+    async fn write_batch(&mut self, writes: &[(u8, u8)]) -> Result<(), DrvError<E>> {
+        for burst in plan::bursts(writes) {
+            self.write_burst(burst.as_bytes()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The read-verify loop for `check_bus_health`, returning whether every
+    /// read matched
+    async fn bus_check_patterns(&mut self) -> Result<bool, DrvError<E>> {
+        for pattern in BUS_CHECK_PATTERNS {
+            self.write_raw(BUS_CHECK_ADDRESS, pattern).await?;
+
+            for _ in 0..BUS_CHECK_READS {
+                let mut read = [0u8; 1];
+                self.read_burst(BUS_CHECK_ADDRESS, &mut read).await?;
+                if read[0] != pattern {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Read consecutive registers starting at `address` in one transaction
+    async fn read_burst(&mut self, address: u8, buf: &mut [u8]) -> Result<(), DrvError<E>> {
+        self.pace().await;
+        self.i2c
+            .write_read(self.address, &[address], buf)
+            .await
+            .map_err(DrvError::I2c)
+    }
+
+    async fn check_id(&mut self, id: u8) -> Result<(), DrvError<E>> {
+        if self.device_info().await?.id != id {
             return Err(DrvError::WrongDeviceId);
         }
 
         Ok(())
     }
 
-    // performs the equivalent operation of power cycling the device. Any
-    // playback operations are immediately interrupted, and all registers are
-    // reset to the default values.
-    async fn reset(&mut self) -> Result<(), DrvError> {
-        let mut mode = ModeReg::default();
-        mode.set_dev_reset(true);
+    /// Actively brake any residual ring after rom playback when
+    /// `RomParams::auto_brake` is set, see `brake`
+    #[cfg(feature = "rom")]
+    async fn auto_brake(&mut self) -> Result<(), DrvError<E>> {
+        if !self.auto_brake {
+            return Ok(());
+        }
+        self.brake().await
+    }
+
+    /// Actively brake the motor by briefly switching to rtp mode with a zero
+    /// duty, whatever mode it was in. The mode and control registers are put
+    /// back afterward, leaving the rtp input at zero.
+    async fn brake(&mut self) -> Result<(), DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        let ctrl3: Control3Reg = self.read().await?;
+
+        // unsigned rtp so a zero duty is full braking
+        let mut unsigned = Control3Reg(ctrl3.value());
+        unsigned.set_data_format_rtp(true);
+        self.write(unsigned).await?;
+        self.write(RealTimePlaybackInputReg(0)).await?;
+
+        let mut braking = ModeReg(mode.value());
+        braking.set_mode(registers::Mode::RealTimePlayback as u8);
+        self.write(braking).await?;
+        self.delay.delay_ms(BRAKE_MS).await;
+
         self.write(mode).await?;
+        self.write(ctrl3).await
+    }
 
-        while self.read::<ModeReg>().await?.dev_reset() {}
+    /// Wait for `effect` to finish playing according to `completion`. `abort`
+    /// is only honoured while polling.
+    #[cfg(feature = "rom")]
+    async fn wait_for_completion(
+        &mut self,
+        effect: Effect,
+        completion: CompletionStrategy,
+        abort: Option<&AtomicBool>,
+    ) -> Result<(), DrvError<E>> {
+        match completion {
+            CompletionStrategy::Poll => self.wait_for_go(abort).await,
+            CompletionStrategy::Timed => {
+                self.delay.delay_ms(effect.estimated_duration_ms()).await;
+                Ok(())
+            }
+            CompletionStrategy::PollAfter(after) => {
+                let after_us = u32::try_from(after.as_micros()).unwrap_or(u32::MAX);
+                self.delay.delay_us(after_us).await;
+                self.wait_for_go(abort).await
+            }
+        }
+    }
 
+    /// Poll the GO bit until the device clears it, giving up with
+    /// `DrvError::Timeout` after `IDLE_TIMEOUT_MS`. If `abort` is set while
+    /// waiting the GO bit is cleared to cancel playback.
+    #[cfg(feature = "rom")]
+    async fn wait_for_go(&mut self, abort: Option<&AtomicBool>) -> Result<(), DrvError<E>> {
+        if self.poll_go(IDLE_TIMEOUT_MS, || aborted(abort)).await? {
+            return self.write(GoReg(0)).await;
+        }
         Ok(())
     }
 
-    /// Send calibration `LoadParams`
-    async fn set_calibration(&mut self, load: LoadParams) -> Result<(), DrvError> {
-        let mut fbcr: FeedbackControlReg = self.read().await?;
-        fbcr.set_bemf_gain(load.back_emf_gain);
-        self.write(fbcr).await?;
+    /// Poll the GO bit every `GO_POLL_INTERVAL_MS` until the device clears it,
+    /// giving up with `DrvError::Timeout` after `timeout_ms`
+    async fn wait_for_idle(&mut self, timeout_ms: u32) -> Result<(), DrvError<E>> {
+        self.poll_go(timeout_ms, || false).await.map(|_| ())
+    }
 
-        let auto_cal_comp = AutoCalibrationCompensationReg(load.compenstation);
-        self.write(auto_cal_comp).await?;
+    /// Poll the GO bit every `GO_POLL_INTERVAL_MS` until the device clears it,
+    /// giving up with `DrvError::Timeout` after `timeout_ms`. Stops early
+    /// once `interrupted` returns true, leaving playback running. Returns
+    /// whether it was interrupted.
+    async fn poll_go(
+        &mut self,
+        timeout_ms: u32,
+        mut interrupted: impl FnMut() -> bool,
+    ) -> Result<bool, DrvError<E>> {
+        for _ in 0..timeout_ms.div_ceil(GO_POLL_INTERVAL_MS) {
+            if !self.go().await? {
+                return Ok(false);
+            }
+            if interrupted() {
+                return Ok(true);
+            }
+            self.delay.delay_ms(GO_POLL_INTERVAL_MS).await;
+        }
 
-        let back_emf = AutoCalibrationCompensationBackEmfReg(load.back_emf);
-        self.write(back_emf).await
+        if self.go().await? {
+            return Err(DrvError::Timeout);
+        }
+        Ok(false)
     }
 
-    /// Run diagnostics
-    async fn diagnostics(&mut self) -> Result<(), DrvError> {
-        let mut mode: ModeReg = self.read().await?;
-        mode.set_standby(false);
-        mode.set_mode(registers::Mode::Diagnostics as u8);
-        self.write(mode).await?;
+    /// Send calibration `LoadParams`
+    async fn set_calibration(&mut self, load: LoadParams) -> Result<(), DrvError<E>> {
+        let feedback: FeedbackControlReg = self.read().await?;
+        self.write_batch(plan::calibration(&load, feedback).as_slice())
+            .await
+    }
+
+    /// Run auto calibration which and return the resulting LoadParams
+    async fn calibrate(&mut self) -> Result<LoadParams, DrvError<E>> {
+        let mode: ModeReg = self.read().await?;
+        self.write(plan::calibration_mode(mode)).await?;
 
         self.set_go().await?;
+        self.wait_for_idle(SELF_TIMED_GO_TIMEOUT_MS).await?;
+
+        let status = StatusReg(self.status().await?);
+        let load = self.calibration().await?;
+        let period = match self.lra {
+            true => Some(self.read::<LraPeriodReg>().await?.value()),
+            false => None,
+        };
+        plan::check_calibration(&status, &load, period)?;
+        // an LRA losing resonance lock during calibration falls back to open loop
+        self.calibrated_closed_loop = Some(!status.feedback_controller_timed_out());
+        Ok(load)
+    }
+
+    /// Check if the device's LoadParams have been set in the nonvolatile memory
+    async fn is_otp(&mut self) -> Result<bool, DrvError<E>> {
+        let reg4: Control4Reg = self.read().await?;
+        Ok(reg4.otp_status())
+    }
+}
 
-        //todo timeout
-        while self.read::<GoReg>().await?.go() {}
+/// Stands in for the EN pin when it isn't wired to a GPIO
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoPin;
 
-        let reg = StatusReg(self.status().await?);
-        if reg.diagnostic_result() {
-            return Err(DrvError::DeviceDiagnosticFailed);
-        }
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
 
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
 
-    /// Run auto calibration which and return the resulting LoadParams
-    async fn calibrate(&mut self) -> Result<LoadParams, DrvError> {
-        let mut mode: ModeReg = self.read().await?;
-        mode.set_standby(false);
-        mode.set_mode(registers::Mode::AutoCalibration as u8);
-        self.write(mode).await?;
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Check an optional abort flag
+#[cfg(any(feature = "rom", feature = "rtp"))]
+fn aborted(abort: Option<&AtomicBool>) -> bool {
+    abort.is_some_and(|abort| abort.load(Ordering::Relaxed))
+}
+
+/// Square root by Newton's method as `f32::sqrt` isn't available in core
+fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    let mut root = x.max(1.0);
+    for _ in 0..20 {
+        root = 0.5 * (root + x / root);
+    }
+    root
+}
+
+/// Possible runtime errors, with `I2c` passing along the error from the bus
+#[allow(unused)]
+#[derive(Debug)]
+pub enum DrvError<E> {
+    I2c(E),
+    WrongMotorType,
+    WrongDeviceId,
+    DeviceDiagnosticFailed,
+    CalibrationFailed,
+    OTPNotProgrammed,
+    InvalidParameter,
+    MotorTypeMismatch,
+    WiringTestFailed,
+    ThermalBaselineMissing,
+    EffectLibraryMismatch,
+    NoMotorResponse,
+    UnsupportedModeForMotor,
+    BusUnreliable,
+    WrongMode,
+    CalibrationMismatch,
+    ResetTimeout,
+    Timeout,
+    SequenceTooLong,
+    OTPAlreadyProgrammed,
+    OTPProgramFailed,
+    EnablePin(PinErrorKind),
+}
+
+// hand written so the error stays formattable whatever the bus error type is,
+// the bus error itself is elided
+#[cfg(feature = "defmt")]
+impl<E> ::defmt::Format for DrvError<E> {
+    fn format(&self, f: ::defmt::Formatter) {
+        match self {
+            DrvError::I2c(_) => ::defmt::write!(f, "I2c"),
+            DrvError::WrongMotorType => ::defmt::write!(f, "WrongMotorType"),
+            DrvError::WrongDeviceId => ::defmt::write!(f, "WrongDeviceId"),
+            DrvError::DeviceDiagnosticFailed => ::defmt::write!(f, "DeviceDiagnosticFailed"),
+            DrvError::CalibrationFailed => ::defmt::write!(f, "CalibrationFailed"),
+            DrvError::OTPNotProgrammed => ::defmt::write!(f, "OTPNotProgrammed"),
+            DrvError::InvalidParameter => ::defmt::write!(f, "InvalidParameter"),
+            DrvError::MotorTypeMismatch => ::defmt::write!(f, "MotorTypeMismatch"),
+            DrvError::WiringTestFailed => ::defmt::write!(f, "WiringTestFailed"),
+            DrvError::ThermalBaselineMissing => ::defmt::write!(f, "ThermalBaselineMissing"),
+            DrvError::EffectLibraryMismatch => ::defmt::write!(f, "EffectLibraryMismatch"),
+            DrvError::NoMotorResponse => ::defmt::write!(f, "NoMotorResponse"),
+            DrvError::UnsupportedModeForMotor => ::defmt::write!(f, "UnsupportedModeForMotor"),
+            DrvError::BusUnreliable => ::defmt::write!(f, "BusUnreliable"),
+            DrvError::WrongMode => ::defmt::write!(f, "WrongMode"),
+            DrvError::CalibrationMismatch => ::defmt::write!(f, "CalibrationMismatch"),
+            DrvError::ResetTimeout => ::defmt::write!(f, "ResetTimeout"),
+            DrvError::Timeout => ::defmt::write!(f, "Timeout"),
+            DrvError::SequenceTooLong => ::defmt::write!(f, "SequenceTooLong"),
+            DrvError::OTPAlreadyProgrammed => ::defmt::write!(f, "OTPAlreadyProgrammed"),
+            DrvError::OTPProgramFailed => ::defmt::write!(f, "OTPProgramFailed"),
+            DrvError::EnablePin(kind) => ::defmt::write!(f, "EnablePin({})", kind),
+        }
+    }
+}
+
+/// Errors from validating configuration, which doesn't touch the bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum ParamError {
+    InvalidParameter,
+    SequenceTooLong,
+}
+
+impl<E> From<ParamError> for DrvError<E> {
+    fn from(err: ParamError) -> Self {
+        match err {
+            ParamError::InvalidParameter => DrvError::InvalidParameter,
+            ParamError::SequenceTooLong => DrvError::SequenceTooLong,
+        }
+    }
+}
+
+/// The fixed address of the driver.  All drivers share the same address so
+/// that it is possible to broadcast on the bus and have multiple units emit the
+/// same waveform. Use `Drv2605l::new_with_address` to reach a device behind an
+/// address translator instead.
+pub const DEFAULT_ADDRESS: u8 = 0x5a;
+
+/// How long the device takes to wake up after EN goes high
+const ENABLE_SETTLE_US: u32 = 250;
+
+/// How many times `program_otp` polls for programming to complete
+const OTP_PROGRAM_POLL_ATTEMPTS: u32 = 10;
+
+/// Pause between `program_otp` polls
+const OTP_PROGRAM_POLL_INTERVAL_MS: u32 = 10;
+
+/// How long the device needs after `DEV_RESET` before it's worth polling
+const RESET_SETTLE_US: u32 = 250;
+
+/// How many times `reset` polls for the device to come back
+const RESET_POLL_ATTEMPTS: u32 = 20;
+
+/// Pause between `reset` polls, 5ms in total with `RESET_POLL_ATTEMPTS`
+const RESET_POLL_INTERVAL_US: u32 = 250;
+
+/// The supply voltage `set_intensity_compensated` treats as full strength,
+/// that of a fully charged single cell lithium battery
+pub const VBAT_REFERENCE_MV: u16 = 4200;
+
+/// How long to let the device drive before sampling the supply voltage
+#[cfg(feature = "rtp")]
+const VBAT_SETTLE_US: u32 = 1000;
+
+/// Microseconds per LRA_PERIOD count
+const LRA_PERIOD_US: f32 = 98.46;
+
+/// LRA_PERIOD values for a resonance between roughly 50 Hz and 500 Hz, far
+/// wider than any real LRA
+const LRA_PERIOD_PLAUSIBLE: RangeInclusive<u8> = 20..=203;
+
+/// Auto calibration results clear of the register limits, a cleared or
+/// saturated result meaning calibration never got a sensible reading
+const CALIBRATION_RESULT_PLAUSIBLE: RangeInclusive<u8> = 0x01..=0xFE;
+
+/// The rtp duty for no drive in open loop bidirectional mode
+#[cfg(feature = "rtp")]
+const WIRING_TEST_ZERO: u8 = 0x80;
+
+/// Time per step of the `wiring_test` ramp, about a second in total
+#[cfg(feature = "rtp")]
+const WIRING_TEST_STEP_MS: u32 = 8;
+
+/// PWM input frequencies the device supports on the IN/TRIG pin, in hertz
+#[cfg(feature = "analog")]
+pub const PWM_INPUT_HZ: RangeInclusive<u32> = 10_000..=250_000;
+
+/// Duty cycle of the `self_test_vibration` burst, firm but short of full scale
+#[cfg(feature = "rtp")]
+const SELF_TEST_DUTY: u8 = 0xC0;
+
+/// How long the `self_test_vibration` burst drives the motor
+#[cfg(feature = "rtp")]
+const SELF_TEST_MS: u32 = 100;
+
+/// Most registers `write_batch` coalesces into one transaction
+const BATCH_MAX: usize = 16;
+
+/// Register `check_bus_health` scribbles on, the last waveform sequencer slot
+const BUS_CHECK_ADDRESS: u8 = 0x0b;
+
+/// Alternating bit patterns written by `check_bus_health`
+const BUS_CHECK_PATTERNS: [u8; 2] = [0x55, 0xaa];
+
+/// How many times `check_bus_health` reads back each pattern
+const BUS_CHECK_READS: usize = 8;
+
+/// How far the compensation and back-EMF results may drift before
+/// `ensure_calibrated` rewrites them
+pub const CALIBRATION_TOLERANCE: u8 = 2;
+
+/// How long `ensure_calibrated` waits before reading back a rewrite
+const CALIBRATION_SETTLE_US: u32 = 1000;
 
-        self.set_go().await?;
+/// How long active braking lasts, on `stop` or after rom playback with
+/// `RomParams::auto_brake`
+const BRAKE_MS: u32 = 20;
 
-        //todo timeout
-        while self.read::<GoReg>().await?.go() {}
+/// How often to poll the GO bit while waiting for playback to complete
+const GO_POLL_INTERVAL_MS: u32 = 1;
 
-        let reg = StatusReg(self.status().await?);
-        if reg.diagnostic_result() {
-            return Err(DrvError::CalibrationFailed);
-        }
+/// How long to wait on auto calibration or diagnostics, which take about a
+/// second at the longest `auto_cal_time`
+const SELF_TIMED_GO_TIMEOUT_MS: u32 = 2000;
 
-        self.calibration().await
-    }
+/// How long `wait_until_idle` and rom playback wait on the GO bit, enough
+/// for all eight sequencer slots holding the longest wait
+const IDLE_TIMEOUT_MS: u32 = 8 * 1270;
 
-    /// Check if the device's LoadParams have been set in the nonvolatile memory
-    async fn is_otp(&mut self) -> Result<bool, DrvError> {
-        let reg4: Control4Reg = self.read().await?;
-        Ok(reg4.otp_status())
-    }
-}
+/// How long after a GO before the GO bit reliably reflects playback, a
+/// little over the 1ms minimum playback interval
+const GO_SETTLE_US: u32 = 1200;
 
-/// Possible runtime errors
-#[allow(unused)]
-#[derive(Debug)]
-#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
-pub enum DrvError {
-    WrongMotorType,
-    WrongDeviceId,
-    ConnectionError,
-    DeviceDiagnosticFailed,
-    CalibrationFailed,
-    OTPNotProgrammed,
-}
+/// Modes that play waveforms from the rom sequencer
+#[cfg(feature = "rom")]
+const TRIGGERED_MODES: [registers::Mode; 3] = [
+    registers::Mode::InternalTrigger,
+    registers::Mode::ExternalTriggerRisingEdge,
+    registers::Mode::ExternalTriggerLevel,
+];
 
-/// The hardcoded address of the driver.  All drivers share the same address so
-/// that it is possible to broadcast on the bus and have multiple units emit the
-/// same waveform
-const ADDRESS: u8 = 0x5a;
+/// Modes where setting the GO bit starts something
+const GO_MODES: [registers::Mode; 5] = [
+    registers::Mode::InternalTrigger,
+    registers::Mode::ExternalTriggerRisingEdge,
+    registers::Mode::ExternalTriggerLevel,
+    registers::Mode::Diagnostics,
+    registers::Mode::AutoCalibration,
+];
 
 /// Selection of calibration options required for initial device construction
 #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
@@ -394,8 +2147,36 @@ pub enum Calibration {
     Otp,
 }
 
+impl Calibration {
+    fn source(&self) -> CalibrationSource {
+        match self {
+            Calibration::Auto(_) => CalibrationSource::Auto,
+            Calibration::Load(_) => CalibrationSource::Load,
+            Calibration::Otp => CalibrationSource::Otp,
+        }
+    }
+}
+
+/// Which `Calibration` option the device was constructed with, kept around for
+/// diagnostics after the calibration values themselves are consumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum CalibrationSource {
+    /// Freshly auto calibrated during construction or by
+    /// `Drv2605l::recalibrate`
+    Auto,
+    /// Previously calibrated values were loaded
+    Load,
+    /// Values came from the nonvolatile memory
+    Otp,
+    /// Restored along with the rest of the configuration by
+    /// `Drv2605l::new_preconfigured`
+    Snapshot,
+}
+
 /// Previously computed calibration parameters. Can be fetched after calibration
 /// and hardcoded during construction instead of auto calibration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct LoadParams {
     /// Auto-Calibration Compensation Result
@@ -406,6 +2187,15 @@ pub struct LoadParams {
     pub back_emf_gain: u8,
 }
 
+impl LoadParams {
+    /// Equal to `other` within `CALIBRATION_TOLERANCE`
+    fn matches(&self, other: &LoadParams) -> bool {
+        self.compenstation.abs_diff(other.compenstation) <= CALIBRATION_TOLERANCE
+            && self.back_emf.abs_diff(other.back_emf) <= CALIBRATION_TOLERANCE
+            && self.back_emf_gain == other.back_emf_gain
+    }
+}
+
 /// Calibration configuration for both ERM and LRA motor types. Some params
 /// really need to be computed from the drv2605l and motor datasheets,
 /// especially for LRA motors
@@ -434,8 +2224,28 @@ pub struct CalibrationParams {
     pub lra_zc_det_time: u8,
 }
 
+/// ERM leaning defaults, see `CalibrationParams::default_for` for LRA motors
 impl Default for CalibrationParams {
     fn default() -> Self {
+        Self::default_for(false)
+    }
+}
+
+impl CalibrationParams {
+    /// Defaults for the given motor type. The ERM defaults are the register
+    /// reset values. The LRA defaults are conservative, assuming an LRA
+    /// resonating around 175 Hz and driving it at about 1.5 Vrms. The rated
+    /// voltage accounts for the LRA sampling window per datasheet 8.5.2.1, the
+    /// clamp leaves some overdrive headroom above that and the drive time is
+    /// about half the resonant period. A common 2 Vrms LRA at 175 Hz needs a
+    /// rated voltage of 0x71 instead, see
+    /// `CalibrationParamsBuilder::rated_voltage`.
+    pub fn default_for(lra: bool) -> Self {
+        let (rated_voltage, overdrive_voltage_clamp, drive_time) = match lra {
+            true => (0x53, 0x89, 0x17),
+            false => (0x3E, 0x8C, 0x13),
+        };
+
         Self {
             brake_factor: 2,
             loop_gain: 2,
@@ -444,15 +2254,346 @@ impl Default for CalibrationParams {
             lra_idiss_time: 1,
             auto_cal_time: 3,
             lra_zc_det_time: 0,
-            rated_voltage: 0x3E,
-            overdrive_voltage_clamp: 0x8C,
-            drive_time: 0x13,
+            rated_voltage,
+            overdrive_voltage_clamp,
+            drive_time,
+        }
+    }
+
+    /// Tone calibration down for places where a loud buzz would be
+    /// disruptive. Auto calibration runs for the shortest time and the
+    /// overdrive clamp is lowered to the rated voltage so there is no
+    /// overdrive kick. The results are less accurate and sluggish motors may
+    /// fail to calibrate at all, so prefer the regular parameters where noise
+    /// doesn't matter.
+    pub fn quiet(self) -> Self {
+        Self {
+            auto_cal_time: 0,
+            overdrive_voltage_clamp: self.rated_voltage,
+            ..self
+        }
+    }
+
+    /// The exact (address, value) register writes `Drv2605l::new` performs,
+    /// in address order, before running `Calibration::Auto`, for storing or
+    /// diffing a complete motor profile
+    pub fn to_register_image(&self, lra: bool) -> RegisterImage {
+        let mut feedback: FeedbackControlReg = Default::default();
+        let mut ctrl2: Control2Reg = Default::default();
+        let mut ctrl4: Control4Reg = Default::default();
+        let mut ctrl1: Control1Reg = Default::default();
+
+        let rated = RatedVoltageReg(self.rated_voltage);
+        let clamp = OverdriveClampReg(self.overdrive_voltage_clamp);
+
+        feedback.set_fb_brake_factor(self.brake_factor);
+        feedback.set_loop_gain(self.loop_gain);
+        if lra {
+            feedback.set_n_erm_lra(true);
+        }
+        ctrl2.set_sample_time(self.lra_sample_time);
+        ctrl2.set_blanking_time(self.lra_blanking_time);
+        ctrl2.set_idiss_time(self.lra_idiss_time);
+        ctrl4.set_auto_cal_time(self.auto_cal_time);
+        ctrl4.set_zc_det_time(self.lra_zc_det_time);
+        ctrl1.set_drive_time(self.drive_time);
+
+        [
+            (RatedVoltageReg::ADDRESS, rated.value()),
+            (OverdriveClampReg::ADDRESS, clamp.value()),
+            (FeedbackControlReg::ADDRESS, feedback.value()),
+            (Control1Reg::ADDRESS, ctrl1.value()),
+            (Control2Reg::ADDRESS, ctrl2.value()),
+            (Control4Reg::ADDRESS, ctrl4.value()),
+        ]
+    }
+
+    /// Parse an image produced by `to_register_image` back into the
+    /// `CalibrationParams` and whether it was for an LRA motor. Returns
+    /// `ParamError::InvalidParameter` if the addresses aren't the expected ones
+    /// in the expected order.
+    pub fn from_register_image(image: &RegisterImage) -> Result<(Self, bool), ParamError> {
+        let addresses = [
+            RatedVoltageReg::ADDRESS,
+            OverdriveClampReg::ADDRESS,
+            FeedbackControlReg::ADDRESS,
+            Control1Reg::ADDRESS,
+            Control2Reg::ADDRESS,
+            Control4Reg::ADDRESS,
+        ];
+        if image.iter().map(|(address, _)| *address).ne(addresses) {
+            return Err(ParamError::InvalidParameter);
+        }
+
+        let feedback = FeedbackControlReg(image[2].1);
+        let ctrl1 = Control1Reg(image[3].1);
+        let ctrl2 = Control2Reg(image[4].1);
+        let ctrl4 = Control4Reg(image[5].1);
+
+        let params = Self {
+            rated_voltage: image[0].1,
+            overdrive_voltage_clamp: image[1].1,
+            drive_time: ctrl1.drive_time(),
+            brake_factor: feedback.fb_brake_factor(),
+            loop_gain: feedback.loop_gain(),
+            auto_cal_time: ctrl4.auto_cal_time(),
+            lra_sample_time: ctrl2.sample_time(),
+            lra_blanking_time: ctrl2.blanking_time(),
+            lra_idiss_time: ctrl2.idiss_time(),
+            lra_zc_det_time: ctrl4.zc_det_time(),
+        };
+
+        Ok((params, feedback.n_erm_lra()))
+    }
+
+    /// Start building `CalibrationParams` from the defaults
+    pub fn builder() -> CalibrationParamsBuilder {
+        CalibrationParamsBuilder::new()
+    }
+
+    /// Check every field fits within its register bitfield
+    fn is_valid(&self) -> bool {
+        self.drive_time <= 0x1F
+            && self.brake_factor <= 7
+            && self.loop_gain <= 3
+            && self.auto_cal_time <= 3
+            && self.lra_sample_time <= 3
+            && self.lra_blanking_time <= 3
+            && self.lra_idiss_time <= 3
+            && self.lra_zc_det_time <= 3
+    }
+}
+
+/// The (address, value) register writes for a `CalibrationParams`, see
+/// `CalibrationParams::to_register_image`
+pub type RegisterImage = [(u8, u8); 6];
+
+/// Builder for `CalibrationParams`. Any field left unset keeps its `Default`
+/// value and `build` validates the whole set against the register ranges.
+#[derive(Default)]
+pub struct CalibrationParamsBuilder {
+    params: CalibrationParams,
+}
+
+impl CalibrationParamsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Datasheet 8.5.2.1 Rated Voltage Programming
+    pub fn rated_voltage(mut self, rated_voltage: u8) -> Self {
+        self.params.rated_voltage = rated_voltage;
+        self
+    }
+
+    /// Datasheet 8.5.2.2 Overdrive Voltage-Clamp Programming
+    pub fn overdrive_voltage_clamp(mut self, overdrive_voltage_clamp: u8) -> Self {
+        self.params.overdrive_voltage_clamp = overdrive_voltage_clamp;
+        self
+    }
+
+    /// Datasheet 8.5.1.1 Drive-Time Programming, 0 to 31
+    pub fn drive_time(mut self, drive_time: u8) -> Self {
+        self.params.drive_time = drive_time;
+        self
+    }
+
+    /// Brake Factor, 0 to 7
+    pub fn brake_factor(mut self, brake_factor: u8) -> Self {
+        self.params.brake_factor = brake_factor;
+        self
+    }
+
+    /// Loop-Gain Control, 0 to 3
+    pub fn loop_gain(mut self, loop_gain: u8) -> Self {
+        self.params.loop_gain = loop_gain;
+        self
+    }
+
+    /// Auto Calibration Time Adjustment, 0 to 3
+    pub fn auto_cal_time(mut self, auto_cal_time: u8) -> Self {
+        self.params.auto_cal_time = auto_cal_time;
+        self
+    }
+
+    /// LRA auto-resonance sampling time, 0 to 3
+    pub fn lra_sample_time(mut self, lra_sample_time: u8) -> Self {
+        self.params.lra_sample_time = lra_sample_time;
+        self
+    }
+
+    /// LRA blanking time, 0 to 3
+    pub fn lra_blanking_time(mut self, lra_blanking_time: u8) -> Self {
+        self.params.lra_blanking_time = lra_blanking_time;
+        self
+    }
+
+    /// LRA Current dissipation time, 0 to 3
+    pub fn lra_idiss_time(mut self, lra_idiss_time: u8) -> Self {
+        self.params.lra_idiss_time = lra_idiss_time;
+        self
+    }
+
+    /// LRA Zero Crossing Detect, 0 to 3
+    pub fn lra_zc_det_time(mut self, lra_zc_det_time: u8) -> Self {
+        self.params.lra_zc_det_time = lra_zc_det_time;
+        self
+    }
+
+    /// Returns the `CalibrationParams` or `ParamError::InvalidParameter` if any
+    /// field is out of range for its register
+    pub fn build(self) -> Result<CalibrationParams, ParamError> {
+        if !self.params.is_valid() {
+            return Err(ParamError::InvalidParameter);
+        }
+
+        Ok(self.params)
+    }
+}
+
+/// Builder for a `Drv2605l`, naming the motor type and configuration instead
+/// of passing them positionally to `new`. The motor type must be chosen with
+/// `lra` or `erm`, `build` returns `DrvError::InvalidParameter` otherwise.
+/// Calibration defaults to `Calibration::Auto` with
+/// `CalibrationParams::default_for` the motor, and the address to
+/// `DEFAULT_ADDRESS`.
+pub struct Drv2605lBuilder<I2C, D, EN = NoPin> {
+    i2c: I2C,
+    delay: D,
+    en: Option<EN>,
+    lra: Option<bool>,
+    address: u8,
+    calibration: Option<Calibration>,
+    inter_op_delay_us: u32,
+    strict: bool,
+    global_strength: Option<f32>,
+}
+
+impl<I2C, E, D> Drv2605lBuilder<I2C, D>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+{
+    pub fn new(i2c: I2C, delay: D) -> Self {
+        Self {
+            i2c,
+            delay,
+            en: None,
+            lra: None,
+            address: DEFAULT_ADDRESS,
+            calibration: None,
+            inter_op_delay_us: 0,
+            strict: false,
+            global_strength: None,
+        }
+    }
+}
+
+impl<I2C, E, D, EN> Drv2605lBuilder<I2C, D, EN>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+    EN: OutputPin,
+{
+    /// Drive the EN pin, which is pulled high before anything else, see
+    /// `Drv2605l::enable`
+    pub fn enable_pin<P: OutputPin>(self, en: P) -> Drv2605lBuilder<I2C, D, P> {
+        Drv2605lBuilder {
+            i2c: self.i2c,
+            delay: self.delay,
+            en: Some(en),
+            lra: self.lra,
+            address: self.address,
+            calibration: self.calibration,
+            inter_op_delay_us: self.inter_op_delay_us,
+            strict: self.strict,
+            global_strength: self.global_strength,
+        }
+    }
+
+    /// The motor is a linear resonant actuator
+    pub fn lra(mut self) -> Self {
+        self.lra = Some(true);
+        self
+    }
+
+    /// The motor is an eccentric rotating mass
+    pub fn erm(mut self) -> Self {
+        self.lra = Some(false);
+        self
+    }
+
+    /// See `Drv2605l::new_with_address`
+    pub fn address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    pub fn calibration(mut self, calibration: Calibration) -> Self {
+        self.calibration = Some(calibration);
+        self
+    }
+
+    /// See `Drv2605l::new_with_inter_op_delay`
+    pub fn inter_op_delay_us(mut self, inter_op_delay_us: u32) -> Self {
+        self.inter_op_delay_us = inter_op_delay_us;
+        self
+    }
+
+    /// See `Drv2605l::set_strict`
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Scale every effect by `strength` from the start, see
+    /// `Drv2605l::set_global_strength`
+    pub fn global_strength(mut self, strength: f32) -> Self {
+        self.global_strength = Some(strength);
+        self
+    }
+
+    /// Construct and calibrate the device as `Drv2605l::new` does
+    pub async fn build(self) -> Result<Drv2605l<I2C, E, D, EN>, DrvError<E>> {
+        let lra = self.lra.ok_or(DrvError::InvalidParameter)?;
+        let calibration = self
+            .calibration
+            .unwrap_or(Calibration::Auto(CalibrationParams::default_for(lra)));
+
+        let mut haptic = Drv2605l::init(
+            self.i2c,
+            self.delay,
+            self.en,
+            self.address,
+            calibration,
+            lra,
+            self.inter_op_delay_us,
+        )
+        .await?;
+        haptic.set_strict(self.strict);
+        if let Some(strength) = self.global_strength {
+            haptic.set_global_strength(strength).await?;
         }
+
+        Ok(haptic)
     }
 }
 
+/// Configuration for `Mode::Pwm`
+#[cfg(feature = "analog")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct PwmParams {
+    /// Frequency of the PWM signal driven into the IN/TRIG pin in hertz. The
+    /// device has no frequency dependent settings, but outside of
+    /// `PWM_INPUT_HZ` the duty cycle isn't reliably decoded so `set_mode`
+    /// returns `DrvError::InvalidParameter`.
+    pub input_freq_hint: u32,
+}
+
 /// Advanced configuration for rom waveforms offering time stretching (or time
 /// shrinking) to the built in waveforms
+#[cfg(feature = "rom")]
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub struct RomParams {
@@ -468,8 +2609,12 @@ pub struct RomParams {
     /// granularity of 5 ms, but can be decreased to 1ms by enabling
     /// decrease_playback_interval to 1ms
     pub decrease_playback_interval: bool,
+    /// Actively brake for a moment after the blocking play helpers finish to
+    /// kill any residual LRA ring
+    pub auto_brake: bool,
 }
 
+#[cfg(feature = "rom")]
 impl Default for RomParams {
     fn default() -> Self {
         Self {
@@ -478,10 +2623,483 @@ impl Default for RomParams {
             sustain_negative_offset: 0,
             brake_time_offset: 0,
             decrease_playback_interval: false,
+            auto_brake: false,
+        }
+    }
+}
+
+/// A slot of the waveform sequencer as loaded by `Drv2605l::set_sequence`
+#[cfg(feature = "rom")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum SequenceStep {
+    /// Play an `Effect` from the selected library
+    Effect(Effect),
+    /// Wait for the given number of 10ms units, up to 127
+    Wait(u8),
+}
+
+/// Configuration for `Mode::AudioToVibe`
+#[cfg(feature = "audio")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct AudioToVibeParams {
+    /// Peak detection time, 0: 10ms, 1: 20ms, 2: 30ms, 3: 40ms
+    pub peak_time: u8,
+    /// Low pass filter frequency, 0: 100Hz, 1: 125Hz, 2: 150Hz, 3: 200Hz
+    pub filter: u8,
+    /// Input level (V) below which there is no vibration = min_input * 1.8V / 255
+    pub min_input: u8,
+    /// Input level (V) for the maximum drive = max_input * 1.8V / 255
+    pub max_input: u8,
+    /// Drive (%) at the minimum input level = min_drive / 255 * 100%
+    pub min_drive: u8,
+    /// Drive (%) at the maximum input level = max_drive / 255 * 100%
+    pub max_drive: u8,
+}
+
+#[cfg(feature = "audio")]
+impl Default for AudioToVibeParams {
+    fn default() -> Self {
+        Self {
+            peak_time: 1,
+            filter: 1,
+            min_input: 0x19,
+            max_input: 0xff,
+            min_drive: 0x19,
+            max_drive: 0xff,
+        }
+    }
+}
+
+/// Estimated playback time of a rom sequence from `Drv2605l::set_go_estimated`
+#[cfg(feature = "rom")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct EstimatedDuration {
+    /// Milliseconds from the GO until playback should be done
+    pub ms: u32,
+}
+
+#[cfg(feature = "rom")]
+impl EstimatedDuration {
+    /// The estimate as a `Duration`
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_millis(u64::from(self.ms))
+    }
+}
+
+/// How the blocking play helpers decide an effect has finished
+#[cfg(feature = "rom")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum CompletionStrategy {
+    /// Poll the GO bit until the device clears it. Accurate, but keeps the
+    /// bus busy for the whole effect. Gives up with `DrvError::Timeout` if
+    /// the GO bit never clears, as with `Drv2605l::wait_until_idle`.
+    Poll,
+    /// Sleep for `Effect::estimated_duration_ms` without touching the bus.
+    /// The estimate errs long and doesn't account for a decreased playback
+    /// interval.
+    Timed,
+    /// Sleep for the given time, then poll the GO bit to confirm
+    PollAfter(Duration),
+}
+
+/// An application defined haptic pattern that can be played by name with
+/// `Drv2605l::play_named`, typically kept in a `static` table:
+///
+/// ```ignore
+/// static PATTERNS: [NamedPattern; 1] = [NamedPattern {
+///     name: "double_tap",
+///     slots: &[Effect::SharpClick100, Effect::Delays(8), Effect::SharpClick100],
+/// }];
+///
+/// haptic.play_named(&PATTERNS, "double_tap").await?;
+/// ```
+#[cfg(feature = "rom")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct NamedPattern {
+    /// Name to play the pattern by
+    pub name: &'static str,
+    /// Effects played in order, which may run past the 8 hardware slots
+    pub slots: &'static [Effect],
+}
+
+#[cfg(feature = "rom")]
+impl NamedPattern {
+    /// Find the pattern called `name`
+    pub fn find<'a>(patterns: &'a [NamedPattern], name: &str) -> Option<&'a NamedPattern> {
+        patterns.iter().find(|pattern| pattern.name == name)
+    }
+}
+
+/// Up to 8 effects played one slot at a time by `Drv2605l::play_sequence` so
+/// that it can be paused, for instance by a higher priority event, and resumed
+/// later. The hardware can't report which slot it is playing or resume part way
+/// through a sequence, so each slot is triggered with its own GO which adds a
+/// little latency between effects compared to `set_rom`.
+///
+/// `pause` takes `&self` so it can be called from another task or an
+/// interrupt while the sequence plays.
+#[cfg(feature = "rom")]
+pub struct RomSequence {
+    slots: [Effect; 8],
+    len: u8,
+    next: AtomicU8,
+    paused: AtomicBool,
+}
+
+#[cfg(feature = "rom")]
+impl RomSequence {
+    /// A sequence of up to 8 effects, stopping early at any `Effect::Stop`.
+    /// Returns `ParamError::SequenceTooLong` if there are more than 8.
+    pub fn new(effects: &[Effect]) -> Result<Self, ParamError> {
+        if effects.len() > 8 {
+            return Err(ParamError::SequenceTooLong);
+        }
+
+        let mut slots = [Effect::Stop; 8];
+        slots[..effects.len()].copy_from_slice(effects);
+
+        Ok(Self {
+            slots,
+            len: effects.len() as u8,
+            next: AtomicU8::new(0),
+            paused: AtomicBool::new(false),
+        })
+    }
+
+    /// Ask playback to pause, cancelling the effect currently playing
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// All slots have been played
+    pub fn is_finished(&self) -> bool {
+        self.next_slot().is_none()
+    }
+
+    /// Start again from the first slot next time the sequence is played
+    pub fn rewind(&self) {
+        self.next.store(0, Ordering::Relaxed);
+    }
+
+    fn next_slot(&self) -> Option<Effect> {
+        let next = self.next.load(Ordering::Relaxed);
+        match self.slots[..usize::from(self.len)].get(usize::from(next)) {
+            Some(Effect::Stop) | None => None,
+            Some(effect) => Some(*effect),
+        }
+    }
+
+    fn advance(&self) {
+        let next = self.next.load(Ordering::Relaxed);
+        self.next.store(next + 1, Ordering::Relaxed);
+    }
+}
+
+/// The voltage to acceleration relationship of a motor, usually found in the
+/// motor datasheet, used to drive it at a physical acceleration
+#[cfg(feature = "rtp")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct MotorModel {
+    /// Drive voltage needed per g of acceleration
+    pub volts_per_g: f32,
+    /// The rated voltage the device was calibrated for, which is what a full
+    /// scale duty cycle drives the motor at
+    pub rated_voltage: f32,
+}
+
+/// An LRA drive voltage to hold constant across `Drv2605l::set_sample_time`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct LraVoltage {
+    /// Target RMS drive voltage in millivolts
+    pub rms_mv: u16,
+    /// Resonant frequency of the LRA in hertz
+    pub frequency_hz: u16,
+}
+
+impl LraVoltage {
+    /// The rated voltage register value for this voltage at `sample_time`,
+    /// per datasheet 8.5.2.1 Rated Voltage Programming
+    fn rated_voltage(&self, sample_time: u8) -> Result<u8, ParamError> {
+        let sample_us = 150.0 + 50.0 * f32::from(sample_time);
+        let duty = 1.0 - (4.0 * sample_us + 300.0) * 1e-6 * f32::from(self.frequency_hz);
+        if duty <= 0.0 {
+            return Err(ParamError::InvalidParameter);
+        }
+
+        let rated = f32::from(self.rms_mv) / (20.58 * sqrt(duty));
+        if rated > 255.0 {
+            return Err(ParamError::InvalidParameter);
+        }
+        Ok(rated as u8)
+    }
+}
+
+/// Noise gate threshold for PWM and analog inputs
+#[cfg(feature = "analog")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum NoiseGateThreshold {
+    Disabled = 0,
+    Percent2 = 1,
+    /// Device default
+    Percent4 = 2,
+    Percent8 = 3,
+}
+
+#[cfg(feature = "analog")]
+impl From<u8> for NoiseGateThreshold {
+    fn from(val: u8) -> NoiseGateThreshold {
+        match val {
+            0 => NoiseGateThreshold::Disabled,
+            1 => NoiseGateThreshold::Percent2,
+            2 => NoiseGateThreshold::Percent4,
+            3 => NoiseGateThreshold::Percent8,
+            _ => unreachable!("impossible NoiseGateThreshold value"),
+        }
+    }
+}
+
+/// Number of failed auto-resonance attempts before an LRA transitions to open
+/// loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum AutoOlCnt {
+    Attempts3 = 0,
+    Attempts4 = 1,
+    /// Device default
+    Attempts5 = 2,
+    Attempts6 = 3,
+}
+
+impl From<u8> for AutoOlCnt {
+    fn from(val: u8) -> AutoOlCnt {
+        match val {
+            0 => AutoOlCnt::Attempts3,
+            1 => AutoOlCnt::Attempts4,
+            2 => AutoOlCnt::Attempts5,
+            3 => AutoOlCnt::Attempts6,
+            _ => unreachable!("impossible AutoOlCnt value"),
+        }
+    }
+}
+
+/// How `stop` brings the motor to rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub enum BrakeMode {
+    /// Let the motor spin down on its own by putting the device in standby,
+    /// which releases the output. Use `set_standby(false)` before playing
+    /// again. Sometimes preferable for ERM motors.
+    Coast,
+    /// Briefly switch to rtp mode with a zero duty cycle so the closed loop
+    /// feedback actively brakes the motor, then restore the previous mode.
+    /// Kills LRA ring out for a crisp release. Leaves the rtp input at zero.
+    Active,
+}
+
+/// Audio-to-vibe configuration as read back from the device
+#[cfg(feature = "audio")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct AtvConfig {
+    /// Peak detection time, 0: 10ms, 1: 20ms, 2: 30ms, 3: 40ms
+    pub peak_time: u8,
+    /// Low pass filter frequency, 0: 100Hz, 1: 125Hz, 2: 150Hz, 3: 200Hz
+    pub filter: u8,
+    /// Minimum input level (V) = min_input * 1.8V / 255
+    pub min_input: u8,
+    /// Maximum input level (V) = max_input * 1.8V / 255
+    pub max_input: u8,
+    /// Minimum output drive (%) = min_drive / 255 * 100%
+    pub min_drive: u8,
+    /// Maximum output drive (%) = max_drive / 255 * 100%
+    pub max_drive: u8,
+    /// Common mode drive for an AC coupled input is enabled
+    pub ac_couple: bool,
+}
+
+/// Readings taken by `Drv2605l::diagnostics`, mostly raw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct DiagnosticMeasurement {
+    /// The actuator is present, not shorted and gave in range back-EMF
+    pub passed: bool,
+    /// Overcurrent was detected, suggesting a low actuator impedance
+    pub over_current: bool,
+    /// The device overheated and shut down, rather than the actuator being at
+    /// fault
+    pub over_temp: bool,
+    /// The feedback controller timed out during the diagnostic
+    pub feedback_timed_out: bool,
+    /// Supply voltage while driving, VDD (V) = vbat * 5.6V / 255
+    pub vbat: u8,
+    /// LRA resonance period, period (us) = lra_period * 98.46us. Only
+    /// meaningful for LRA motors.
+    pub lra_period: u8,
+}
+
+/// Feedback tuning as read by `Drv2605l::feedback_params`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FeedbackParams {
+    /// Brake factor, 0: 1x, 1: 2x, 2: 3x, 3: 4x, 4: 6x, 5: 8x, 6: 16x,
+    /// 7: braking disabled
+    pub brake_factor: u8,
+    /// Loop gain, 0: low, 1: medium, 2: high, 3: very high
+    pub loop_gain: u8,
+}
+
+/// The status register as read by `Drv2605l::read_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Status {
+    /// Device id, 3: DRV2605, 4: DRV2604, 6: DRV2604L, 7: DRV2605L
+    pub device_id: u8,
+    /// The last auto calibration failed or diagnostic found a fault
+    pub diagnostic_result: bool,
+    /// The device got too hot and shut down
+    pub over_temp: bool,
+    /// The load impedance is too low and the device shut down, it keeps
+    /// retrying until the impedance recovers
+    pub over_current: bool,
+    /// The feedback controller lost back-EMF or LRA frequency lock
+    pub feedback_timed_out: bool,
+}
+
+impl From<StatusReg> for Status {
+    fn from(reg: StatusReg) -> Self {
+        Self {
+            device_id: reg.device_id(),
+            diagnostic_result: reg.diagnostic_result(),
+            over_temp: reg.over_temp(),
+            over_current: reg.oc_detected(),
+            feedback_timed_out: reg.feedback_controller_timed_out(),
+        }
+    }
+}
+
+/// Identity of the part as read by `Drv2605l::device_info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct DeviceInfo {
+    /// Device id, 3: DRV2605, 4: DRV2604, 6: DRV2604L, 7: DRV2605L
+    pub id: u8,
+    /// The whole status register the id was decoded from, DEVICE_ID in bits
+    /// 7:5, for telling apart any future parts sharing an id
+    pub status: u8,
+}
+
+impl DeviceInfo {
+    /// The part name for known device ids
+    pub fn part(&self) -> Option<&'static str> {
+        match self.id {
+            3 => Some("DRV2605"),
+            4 => Some("DRV2604"),
+            6 => Some("DRV2604L"),
+            7 => Some("DRV2605L"),
+            _ => None,
         }
     }
 }
 
+/// Results of `Drv2605l::probe`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct ProbeResult {
+    /// Device id read from the status register
+    pub device_id: u8,
+    /// The device id is that of a DRV2605L
+    pub device_id_ok: bool,
+    /// The device is configured for the motor type it was constructed with
+    pub motor_type_ok: bool,
+    /// Overcurrent was detected
+    pub over_current: bool,
+    /// Overtemperature was detected
+    pub over_temp: bool,
+    /// The calibration compensation and back-EMF results are neither cleared,
+    /// saturated nor left at their power on values
+    pub calibration_plausible: bool,
+    /// Calibration values are programmed into the nonvolatile memory
+    pub otp_programmed: bool,
+}
+
+impl ProbeResult {
+    /// The right chip, configured for the right motor, without faults and
+    /// with plausible calibration. OTP status isn't considered.
+    pub fn is_healthy(&self) -> bool {
+        self.device_id_ok
+            && self.motor_type_ok
+            && !self.over_current
+            && !self.over_temp
+            && self.calibration_plausible
+    }
+}
+
+/// Everything about the device's configuration and state in one place, as
+/// returned by `Drv2605l::full_report`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct DeviceReport {
+    /// Configured for an LRA rather than an ERM motor
+    pub lra: bool,
+    /// How the device was calibrated
+    pub calibration_source: CalibrationSource,
+    /// Calibration values currently in use
+    pub calibration: LoadParams,
+    /// Rated voltage register
+    pub rated_voltage: u8,
+    /// Overdrive clamp voltage register
+    pub overdrive_clamp: u8,
+    /// Feedback brake factor, see `CalibrationParams::brake_factor`
+    pub brake_factor: u8,
+    /// Feedback loop gain, see `CalibrationParams::loop_gain`
+    pub loop_gain: u8,
+    /// Mode the device is operating in
+    pub mode: OperatingMode,
+    /// Device is in standby
+    pub standby: bool,
+    /// Overcurrent was detected
+    pub over_current: bool,
+    /// Overtemperature was detected
+    pub over_temp: bool,
+    /// The feedback controller timed out
+    pub feedback_timed_out: bool,
+}
+
+/// Raw register contents captured by `snapshot_config`. Plain bytes so the
+/// snapshot can be persisted and handed back to `restore_config` later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct ConfigSnapshot {
+    /// Mode register
+    pub mode: u8,
+    /// Library selection register
+    pub library: u8,
+    /// Rated voltage register
+    pub rated_voltage: u8,
+    /// Overdrive clamp voltage register
+    pub overdrive_clamp: u8,
+    /// Auto-Calibration Compensation Result
+    pub compensation: u8,
+    /// Auto-Calibration Back-EMF Result
+    pub back_emf: u8,
+    /// Feedback control register
+    pub feedback: u8,
+    /// Control registers 1 through 5
+    pub control: [u8; 5],
+}
+
 /// Selection of modes of device operation, some of which take their
 /// configuration via the enum
 #[derive(Debug, Clone, Copy)]
@@ -492,22 +3110,74 @@ pub enum Mode {
     /// all ERM libraries are tuned for open loop.
     ///
     /// Use set rom setters and then GO bit to play an `Effect`
+    #[cfg(feature = "rom")]
     Rom(Library, RomParams),
+    /// Like `Rom`, but playback is triggered by a rising edge on the IN/TRIG
+    /// pin rather than `set_go`. Each edge plays the waveform sequence through
+    /// once, but a second rising edge before it finishes cancels playback
+    /// rather than restarting it.
+    #[cfg(feature = "rom")]
+    RomExternalEdge(Library, RomParams),
+    /// Like `Rom`, but the IN/TRIG pin drives playback rather than `set_go`.
+    /// The waveform sequence plays while the pin is held high and is cut
+    /// short when it goes low.
+    #[cfg(feature = "rom")]
+    RomExternalLevel(Library, RomParams),
     /// Enable Pulse Width Modulated mod (closed loop unidirectional )
     ///
     /// 0% full braking, 50% 1/2 Rated Voltage, 100% Rated Voltage
-    Pwm,
+    #[cfg(feature = "analog")]
+    Pwm(PwmParams),
     /// Set analog input mode.
     ///
     /// Send an analog voltage to the IN/TRIG to set a duty cycle which will
     /// persist until mode change or standby. The reference voltage in standby
     /// mode is 1.8 V thus 100% is 1.8V, 50% is .9V, 0% is 0V analogous to the
     /// duty-cycle percentage in PWM mode
+    #[cfg(feature = "analog")]
     Analog,
     /// Enable Real Time Playback (closed loop unidirectional unsigned )
     ///
     /// Use `set_rtp` to update the duty cycle which will persist until another
     /// call to `set_rtp`, change to standby, or mode change.
     /// 0x00 full braking, 0x7F 1/2 Rated Voltage, 0xFF Rated Voltage
+    #[cfg(feature = "rtp")]
     RealTimePlayback,
+    /// Enable Real Time Playback with signed data (closed loop bidirectional
+    /// for LRA motors)
+    ///
+    /// Use `set_rtp_signed` to update the drive. 0 is the no drive midpoint,
+    /// 127 Rated Voltage and -128 the full negative end, which brakes, whereas
+    /// in `RealTimePlayback` 0x00 is full braking.
+    #[cfg(feature = "rtp")]
+    RealTimePlaybackSigned,
+    /// Enable audio to vibe mode
+    ///
+    /// An audio signal AC coupled into the IN/TRIG pin is peak detected and
+    /// low pass filtered into a vibration whose strength follows the envelope
+    /// of the audio
+    #[cfg(feature = "audio")]
+    AudioToVibe(AudioToVibeParams),
+}
+
+impl Mode {
+    /// Whether the mode works with an LRA (`lra` true) or ERM motor. Every
+    /// mode can drive either kind of motor, but the rom libraries are tuned for
+    /// one or the other so rom mode needs the LRA library for LRA motors and
+    /// an ERM library for ERM motors.
+    #[cfg_attr(not(feature = "rom"), allow(unused_variables))]
+    pub fn is_supported_for(&self, lra: bool) -> bool {
+        match self {
+            #[cfg(feature = "rom")]
+            Mode::Rom(library, _)
+            | Mode::RomExternalEdge(library, _)
+            | Mode::RomExternalLevel(library, _) => match library {
+                Library::Empty => true,
+                Library::Lra | Library::Auto => lra,
+                _ => !lra,
+            },
+            #[allow(unreachable_patterns)]
+            _ => true,
+        }
+    }
 }
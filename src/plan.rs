@@ -0,0 +1,446 @@
+//! Register writes worked out without touching the bus, shared by the async
+//! driver and the blocking driver in `blocking`. A front end reads the
+//! registers a change depends on, hands them over here and replays the
+//! resulting (address, value) writes, so both drivers sequence the device
+//! identically and only differ in how they wait on the bus.
+
+use crate::registers::{
+    self, AutoCalibrationCompensationBackEmfReg, AutoCalibrationCompensationReg, Control1Reg,
+    Control3Reg, FeedbackControlReg, ModeReg, Register, StatusReg,
+};
+#[cfg(feature = "audio")]
+use crate::registers::{
+    AudioToVibeControlReg, AudioToVibeMaxDriveReg, AudioToVibeMaxInputReg, AudioToVibeMinDriveReg,
+    AudioToVibeMinInputReg,
+};
+#[cfg(feature = "rom")]
+use crate::registers::{
+    BrakeTimeOffsetReg, Control5Reg, GoReg, LibrarySelectionReg, OverdriveTimeOffsetReg,
+    SustainTimeOffsetNegativeReg, SustainTimeOffsetPositiveReg, Waveform0Reg,
+};
+#[cfg(feature = "analog")]
+use crate::PWM_INPUT_HZ;
+use crate::{DrvError, LoadParams, Mode, BATCH_MAX, LRA_PERIOD_PLAUSIBLE};
+#[cfg(feature = "rom")]
+use crate::{Effect, Library, RomParams};
+
+/// The most writes a single plan makes, a rom mode change being the longest
+const WRITES_MAX: usize = 16;
+
+/// (address, value) register writes to make in order
+pub(crate) struct Writes {
+    writes: [(u8, u8); WRITES_MAX],
+    len: usize,
+}
+
+impl Writes {
+    pub(crate) fn new() -> Self {
+        Self {
+            writes: [(0, 0); WRITES_MAX],
+            len: 0,
+        }
+    }
+
+    /// Queue writing `register`
+    pub(crate) fn push<REG>(&mut self, register: REG)
+    where
+        REG: Register,
+    {
+        self.push_raw(REG::ADDRESS, register.value());
+    }
+
+    /// Queue writing `value` to the register at `address`
+    pub(crate) fn push_raw(&mut self, address: u8, value: u8) {
+        self.writes[self.len] = (address, value);
+        self.len += 1;
+    }
+
+    pub(crate) fn as_slice(&self) -> &[(u8, u8)] {
+        &self.writes[..self.len]
+    }
+}
+
+/// A single write transaction, an address followed by values for it and the
+/// registers after it
+pub(crate) struct Burst {
+    buf: [u8; BATCH_MAX + 1],
+    len: usize,
+}
+
+impl Burst {
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+/// Split (address, value) writes into transactions, coalescing each run of
+/// consecutive addresses into a single burst of up to `BATCH_MAX` registers
+pub(crate) fn bursts(writes: &[(u8, u8)]) -> impl Iterator<Item = Burst> + '_ {
+    let mut rest = writes;
+    core::iter::from_fn(move || {
+        let &(start, _) = rest.first()?;
+        let run = rest
+            .iter()
+            .enumerate()
+            .take_while(|(i, (address, _))| usize::from(*address) == usize::from(start) + i)
+            .count()
+            .min(BATCH_MAX);
+
+        let mut buf = [0u8; BATCH_MAX + 1];
+        buf[0] = start;
+        for (byte, (_, value)) in buf[1..].iter_mut().zip(&rest[..run]) {
+            *byte = *value;
+        }
+        rest = &rest[run..];
+
+        Some(Burst { buf, len: run + 1 })
+    })
+}
+
+/// The registers a mode change depends on, read as two bursts starting at
+/// `ModeRegs::LOW` and `ModeRegs::CONTROL`
+pub(crate) struct ModeRegs {
+    mode: u8,
+    #[cfg(feature = "rom")]
+    library: u8,
+    #[cfg(any(feature = "rom", feature = "analog", feature = "audio"))]
+    ctrl1: u8,
+    ctrl3: u8,
+    #[cfg(feature = "rom")]
+    ctrl5: u8,
+}
+
+impl ModeRegs {
+    /// Start of the mode, rtp input and library registers
+    pub(crate) const LOW: u8 = ModeReg::ADDRESS;
+    /// Start of the five control registers
+    pub(crate) const CONTROL: u8 = Control1Reg::ADDRESS;
+
+    pub(crate) fn new(low: [u8; 3], control: [u8; 5]) -> Self {
+        Self {
+            mode: low[0],
+            #[cfg(feature = "rom")]
+            library: low[2],
+            #[cfg(any(feature = "rom", feature = "analog", feature = "audio"))]
+            ctrl1: control[0],
+            ctrl3: control[2],
+            #[cfg(feature = "rom")]
+            ctrl5: control[4],
+        }
+    }
+}
+
+/// What entering a mode takes
+pub(crate) struct ModePlan {
+    pub(crate) writes: Writes,
+    /// The library selected by a rom mode, `Library::Auto` resolved, and its
+    /// `RomParams::auto_brake`
+    #[cfg(feature = "rom")]
+    pub(crate) rom: Option<(Library, bool)>,
+}
+
+/// The writes to enter `mode` from the registers in `regs`
+// with every mode compiled out `Mode` has no variants to match
+#[cfg_attr(
+    not(any(
+        feature = "rom",
+        feature = "rtp",
+        feature = "analog",
+        feature = "audio"
+    )),
+    allow(unreachable_code, unused_mut, unused_variables)
+)]
+pub(crate) fn mode<E>(mode: &Mode, lra: bool, regs: &ModeRegs) -> Result<ModePlan, DrvError<E>> {
+    if !mode.is_supported_for(lra) {
+        return Err(DrvError::UnsupportedModeForMotor);
+    }
+
+    let mut m = ModeReg(regs.mode);
+    let mut ctrl3 = Control3Reg(regs.ctrl3);
+    let mut plan = ModePlan {
+        writes: Writes::new(),
+        #[cfg(feature = "rom")]
+        rom: None,
+    };
+    let writes = &mut plan.writes;
+
+    match *mode {
+        #[cfg(feature = "analog")]
+        Mode::Pwm(params) => {
+            if !PWM_INPUT_HZ.contains(&params.input_freq_hint) {
+                return Err(DrvError::InvalidParameter);
+            }
+
+            // unset in case coming from rom mode
+            if !lra {
+                ctrl3.set_erm_open_loop(false);
+            }
+            ctrl3.set_n_pwm_analog(false);
+            writes.push(ctrl3);
+
+            // unset in case coming from audio to vibe mode
+            let mut ctrl1 = Control1Reg(regs.ctrl1);
+            ctrl1.set_ac_couple(false);
+            writes.push(ctrl1);
+
+            m.set_mode(registers::Mode::PwmInputAndAnalogInput as u8);
+            writes.push(m);
+        }
+        #[cfg(feature = "rom")]
+        Mode::Rom(library, options)
+        | Mode::RomExternalEdge(library, options)
+        | Mode::RomExternalLevel(library, options) => {
+            let library = resolve_library(library, lra)?;
+
+            rom_params(writes, &options, Control5Reg(regs.ctrl5));
+
+            // erm requires open loop mode
+            if !lra {
+                ctrl3.set_erm_open_loop(true);
+            }
+            writes.push(ctrl3);
+
+            let trigger = match mode {
+                Mode::RomExternalEdge(..) => registers::Mode::ExternalTriggerRisingEdge,
+                Mode::RomExternalLevel(..) => registers::Mode::ExternalTriggerLevel,
+                _ => registers::Mode::InternalTrigger,
+            };
+            if trigger != registers::Mode::InternalTrigger {
+                // the IN/TRIG pin is digital, unset in case coming from
+                // audio to vibe mode
+                let mut ctrl1 = Control1Reg(regs.ctrl1);
+                ctrl1.set_ac_couple(false);
+                writes.push(ctrl1);
+            }
+
+            m.set_mode(trigger as u8);
+            writes.push(m);
+
+            select_library(writes, library, LibrarySelectionReg(regs.library));
+            plan.rom = Some((library, options.auto_brake));
+        }
+        #[cfg(feature = "analog")]
+        Mode::Analog => {
+            // unset in case coming from rom mode
+            if !lra {
+                ctrl3.set_erm_open_loop(false);
+            }
+            ctrl3.set_n_pwm_analog(true);
+            writes.push(ctrl3);
+
+            // a dc level, unset in case coming from audio to vibe mode
+            let mut ctrl1 = Control1Reg(regs.ctrl1);
+            ctrl1.set_ac_couple(false);
+            writes.push(ctrl1);
+
+            m.set_mode(registers::Mode::PwmInputAndAnalogInput as u8);
+            writes.push(m);
+        }
+        #[cfg(feature = "rtp")]
+        Mode::RealTimePlayback | Mode::RealTimePlaybackSigned => {
+            // no other modes use this bit so it's only ever set here
+            ctrl3.set_data_format_rtp(!matches!(mode, Mode::RealTimePlaybackSigned));
+            // unset in case coming from rom mode
+            if !lra {
+                ctrl3.set_erm_open_loop(false);
+            }
+            writes.push(ctrl3);
+
+            m.set_mode(registers::Mode::RealTimePlayback as u8);
+            writes.push(m);
+        }
+        #[cfg(feature = "audio")]
+        Mode::AudioToVibe(params) => {
+            if params.peak_time > 3 || params.filter > 3 {
+                return Err(DrvError::InvalidParameter);
+            }
+
+            // unset in case coming from rom mode
+            if !lra {
+                ctrl3.set_erm_open_loop(false);
+            }
+            // audio comes in on the analog input path
+            ctrl3.set_n_pwm_analog(true);
+            writes.push(ctrl3);
+
+            let mut ctrl1 = Control1Reg(regs.ctrl1);
+            ctrl1.set_ac_couple(true);
+            writes.push(ctrl1);
+
+            // the five audio to vibe registers are contiguous
+            let mut ctrl = AudioToVibeControlReg(0);
+            ctrl.set_ath_peak_time(params.peak_time);
+            ctrl.set_ath_filter(params.filter);
+            writes.push(ctrl);
+            writes.push(AudioToVibeMinInputReg(params.min_input));
+            writes.push(AudioToVibeMaxInputReg(params.max_input));
+            writes.push(AudioToVibeMinDriveReg(params.min_drive));
+            writes.push(AudioToVibeMaxDriveReg(params.max_drive));
+
+            m.set_mode(registers::Mode::AudioToVibe as u8);
+            writes.push(m);
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Swap `Library::Auto` for the library matching the motor. Returns
+/// `DrvError::UnsupportedModeForMotor` for ERM motors which have no single
+/// matching library, the same error `set_mode` gives them.
+#[cfg(feature = "rom")]
+pub(crate) fn resolve_library<E>(library: Library, lra: bool) -> Result<Library, DrvError<E>> {
+    match library {
+        Library::Auto if lra => Ok(Library::Lra),
+        Library::Auto => Err(DrvError::UnsupportedModeForMotor),
+        library => Ok(library),
+    }
+}
+
+/// Select an already resolved `library`, keeping the rest of `current`
+#[cfg(feature = "rom")]
+pub(crate) fn select_library(writes: &mut Writes, library: Library, current: LibrarySelectionReg) {
+    let mut lib = current;
+    lib.set_library_selection(library as u8);
+    writes.push(lib);
+}
+
+/// Apply rom timing offsets and the playback interval on top of `ctrl5`. The
+/// four offset registers are contiguous so they go out in a single burst.
+#[cfg(feature = "rom")]
+pub(crate) fn rom_params(writes: &mut Writes, params: &RomParams, ctrl5: Control5Reg) {
+    let mut ctrl5 = ctrl5;
+    ctrl5.set_playback_interval(params.decrease_playback_interval);
+    writes.push(ctrl5);
+
+    writes.push(OverdriveTimeOffsetReg(params.overdrive_time_offset));
+    writes.push(SustainTimeOffsetPositiveReg(params.sustain_positive_offset));
+    writes.push(SustainTimeOffsetNegativeReg(params.sustain_negative_offset));
+    writes.push(BrakeTimeOffsetReg(params.brake_time_offset));
+}
+
+/// Load up to 8 `slots` into the waveform sequencer, terminated with
+/// `Effect::Stop` if there are fewer than 8. With `go` every slot is written
+/// and the burst runs on into the GO register, which sits right after them,
+/// to start playback in the same transaction. Returns
+/// `DrvError::SequenceTooLong` for more than 8 slots.
+#[cfg(feature = "rom")]
+pub(crate) fn waveforms<E>(slots: &[Effect], go: bool) -> Result<Writes, DrvError<E>> {
+    if slots.len() > 8 {
+        return Err(DrvError::SequenceTooLong);
+    }
+
+    let len = match go {
+        true => 8,
+        false => (slots.len() + 1).min(8),
+    };
+    let mut writes = Writes::new();
+    for i in 0..len {
+        let slot = slots.get(i).copied().unwrap_or(Effect::Stop);
+        writes.push_raw(Waveform0Reg::ADDRESS + i as u8, slot.into());
+    }
+    if go {
+        let mut reg = GoReg(0);
+        reg.set_go(true);
+        writes.push(reg);
+    }
+
+    Ok(writes)
+}
+
+/// In strict mode reject waveform effects when the empty library is
+/// selected. `Effect::Stop` and `Effect::Delays` don't come from a library so
+/// they're always allowed.
+#[cfg(feature = "rom")]
+pub(crate) fn check_effects<E>(
+    strict: bool,
+    library: Option<Library>,
+    effects: &[Effect],
+) -> Result<(), DrvError<E>> {
+    if !strict || library != Some(Library::Empty) {
+        return Ok(());
+    }
+
+    let waveform = effects
+        .iter()
+        .any(|effect| !matches!(effect, Effect::Stop | Effect::Delays(_)));
+    if waveform {
+        return Err(DrvError::EffectLibraryMismatch);
+    }
+    Ok(())
+}
+
+/// The writes to load calibration results, keeping the rest of `feedback`.
+/// The result registers sit right before the feedback control register so
+/// they all go out in a single burst.
+pub(crate) fn calibration(load: &LoadParams, feedback: FeedbackControlReg) -> Writes {
+    let mut feedback = feedback;
+    feedback.set_bemf_gain(load.back_emf_gain);
+
+    let mut writes = Writes::new();
+    writes.push(AutoCalibrationCompensationReg(load.compenstation));
+    writes.push(AutoCalibrationCompensationBackEmfReg(load.back_emf));
+    writes.push(feedback);
+    writes
+}
+
+/// `mode` switched to auto calibration and out of standby, ready for a GO
+pub(crate) fn calibration_mode(mode: ModeReg) -> ModeReg {
+    let mut mode = mode;
+    mode.set_standby(false);
+    mode.set_mode(registers::Mode::AutoCalibration as u8);
+    mode
+}
+
+/// Check the outcome of auto calibration. Besides the device's own verdict in
+/// `status`, auto calibration can converge on bad values when the declared
+/// motor type doesn't match the physical motor, so `load` is sanity checked
+/// against what the declared motor should produce. `lra_period` is only
+/// needed for LRA motors.
+pub(crate) fn check_calibration<E>(
+    status: &StatusReg,
+    load: &LoadParams,
+    lra_period: Option<u8>,
+) -> Result<(), DrvError<E>> {
+    if status.diagnostic_result() {
+        return Err(DrvError::CalibrationFailed);
+    }
+
+    // no back-EMF at all means calibration never saw the motor respond
+    if load.back_emf == 0 {
+        return Err(DrvError::MotorTypeMismatch);
+    }
+
+    match lra_period {
+        Some(period) if !LRA_PERIOD_PLAUSIBLE.contains(&period) => Err(DrvError::MotorTypeMismatch),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(all(test, feature = "analog", feature = "audio"))]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    /// Enter `mode` from the register file `regs` and apply its writes
+    fn enter(regs: &mut [u8; 0x23], mode: Mode) {
+        let low = [regs[0x01], regs[0x02], regs[0x03]];
+        let control = [regs[0x1B], regs[0x1C], regs[0x1D], regs[0x1E], regs[0x1F]];
+        let plan = super::mode::<Infallible>(&mode, false, &ModeRegs::new(low, control)).unwrap();
+        for &(address, value) in plan.writes.as_slice() {
+            regs[address as usize] = value;
+        }
+    }
+
+    #[test]
+    fn analog_after_audio_to_vibe_is_dc_coupled() {
+        let mut regs = [0u8; 0x23];
+        enter(&mut regs, Mode::AudioToVibe(Default::default()));
+        assert!(Control1Reg(regs[0x1B]).ac_couple());
+
+        enter(&mut regs, Mode::Analog);
+        assert!(!Control1Reg(regs[0x1B]).ac_couple());
+        assert!(Control3Reg(regs[0x1D]).n_pwm_analog());
+        assert!(ModeReg(regs[0x01]).mode() == registers::Mode::PwmInputAndAnalogInput);
+    }
+}
@@ -57,7 +57,7 @@ impl Register for StatusReg {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
 pub enum Mode {
     /// Waveforms are fired by setting the GO bit in register 0x0C.
@@ -148,6 +148,12 @@ impl Default for RatedVoltageReg {
         Self(0x3E)
     }
 }
+impl From<u8> for RatedVoltageReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
 impl Register for RatedVoltageReg {
     const ADDRESS: u8 = 0x16;
     fn value(&self) -> u8 {
@@ -165,6 +171,12 @@ impl Register for OverdriveClampReg {
     }
 }
 
+impl From<u8> for OverdriveClampReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
 impl Default for OverdriveClampReg {
     fn default() -> Self {
         Self(0x8C)
@@ -214,14 +226,17 @@ impl From<u8> for AutoCalibrationCompensationBackEmfReg {
     }
 }
 
+#[cfg(feature = "rom")]
 #[derive(Debug)]
 pub struct OverdriveTimeOffsetReg(pub u8);
 
+#[cfg(feature = "rom")]
 impl Default for OverdriveTimeOffsetReg {
     fn default() -> Self {
         Self(0x0)
     }
 }
+#[cfg(feature = "rom")]
 impl Register for OverdriveTimeOffsetReg {
     const ADDRESS: u8 = 0x0d;
     fn value(&self) -> u8 {
@@ -229,14 +244,17 @@ impl Register for OverdriveTimeOffsetReg {
     }
 }
 
+#[cfg(feature = "rom")]
 #[derive(Debug)]
 pub struct SustainTimeOffsetPositiveReg(pub u8);
 
+#[cfg(feature = "rom")]
 impl Default for SustainTimeOffsetPositiveReg {
     fn default() -> Self {
         Self(0x0)
     }
 }
+#[cfg(feature = "rom")]
 impl Register for SustainTimeOffsetPositiveReg {
     const ADDRESS: u8 = 0x0e;
     fn value(&self) -> u8 {
@@ -244,15 +262,18 @@ impl Register for SustainTimeOffsetPositiveReg {
     }
 }
 
+#[cfg(feature = "rom")]
 #[derive(Debug)]
 pub struct SustainTimeOffsetNegativeReg(pub u8);
 
+#[cfg(feature = "rom")]
 impl Default for SustainTimeOffsetNegativeReg {
     fn default() -> Self {
         Self(0x0)
     }
 }
 
+#[cfg(feature = "rom")]
 impl Register for SustainTimeOffsetNegativeReg {
     const ADDRESS: u8 = 0x0f;
     fn value(&self) -> u8 {
@@ -260,14 +281,24 @@ impl Register for SustainTimeOffsetNegativeReg {
     }
 }
 
+#[cfg(feature = "rom")]
 #[derive(Debug)]
 pub struct BrakeTimeOffsetReg(pub u8);
 
+#[cfg(feature = "rom")]
 impl Default for BrakeTimeOffsetReg {
     fn default() -> Self {
         Self(0x0)
     }
 }
+#[cfg(feature = "rom")]
+impl From<u8> for BrakeTimeOffsetReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(feature = "rom")]
 impl Register for BrakeTimeOffsetReg {
     const ADDRESS: u8 = 0x10;
     fn value(&self) -> u8 {
@@ -307,6 +338,11 @@ pub enum Library {
     Lra = 6,
     /// Rated Voltage 4.5V Overdrive Voltage 5V Rise Time 35-45ms Brake Time 10-20ms
     F = 7,
+    /// Pick the library for the motor when entering rom mode. This is the LRA
+    /// library for LRA motors. The ERM libraries differ by motor so ERM
+    /// motors still need one chosen explicitly, ERM motors given `Auto` get
+    /// `DrvError::UnsupportedModeForMotor`. Never read back from the device.
+    Auto = 8,
 }
 
 impl From<u8> for Library {
@@ -485,6 +521,166 @@ impl From<Effect> for u8 {
     }
 }
 
+impl Effect {
+    /// The `Effect` for a raw waveform sequencer value, as read back from the
+    /// device. Returns `None` for ids past the end of the rom.
+    pub fn from_id(id: u8) -> Option<Effect> {
+        match id {
+            0 => Some(Effect::Stop),
+            id if id & 0x80 != 0 => Some(Effect::Delays(id & 0x7f)),
+            id => Effect::from_library_index(id),
+        }
+    }
+
+    /// The effect number as listed in the Immersion TS2200 library
+    /// documentation and datasheet, which counts from 1 and matches the value
+    /// written to the waveform sequencer. `Effect::Stop` and `Effect::Delays`
+    /// aren't library effects and return 0.
+    pub fn library_index(&self) -> u8 {
+        match self {
+            Effect::Stop | Effect::Delays(_) => 0,
+            effect => u8::from(*effect),
+        }
+    }
+
+    /// The `Effect` for a TS2200 documentation effect number, 1 through 123
+    pub fn from_library_index(index: u8) -> Option<Effect> {
+        ROM_EFFECTS.get(usize::from(index).checked_sub(1)?).copied()
+    }
+
+    /// A rough estimate of how long the effect plays in milliseconds with the
+    /// default 5ms playback interval. The libraries are tuned differently so
+    /// these err on the long side rather than being exact for any one of them.
+    /// `Effect::Delays` is exact.
+    pub fn estimated_duration_ms(&self) -> u32 {
+        match self {
+            Effect::Stop => 0,
+            Effect::Delays(n) => u32::from(*n) * 10,
+            Effect::SharpClick100
+            | Effect::SharpClick60
+            | Effect::SharpClick30
+            | Effect::SharpTickOne100
+            | Effect::SharpTickTwo80
+            | Effect::SharpTickThree60 => 30,
+            Effect::StrongClick100
+            | Effect::StrongClick60
+            | Effect::StrongClick30
+            | Effect::StrongClickOne100
+            | Effect::StrongClickTwo80
+            | Effect::StrongClickThree60
+            | Effect::StrongClickFour30
+            | Effect::MediumClickOne100
+            | Effect::MediumClickTwo80
+            | Effect::MediumClickThree60
+            | Effect::TransitionClickOne100
+            | Effect::TransitionClickTwo80
+            | Effect::TransitionClickThree60
+            | Effect::TransitionClickFour40
+            | Effect::TransitionClickFive20
+            | Effect::TransitionClickSix10 => 60,
+            Effect::SoftBump100
+            | Effect::SoftBump60
+            | Effect::SoftBump30
+            | Effect::ShortDoubleSharpTickOne100
+            | Effect::ShortDoubleSharpTickTwo80
+            | Effect::ShortDoubleSharpTickThree60 => 100,
+            Effect::TransitionHumOne100
+            | Effect::TransitionHumTwo80
+            | Effect::TransitionHumThree60
+            | Effect::TransitionHumFour40
+            | Effect::TransitionHumFive20
+            | Effect::TransitionHumSix10
+            | Effect::TransitionRampDownShortSmoothOne100to0
+            | Effect::TransitionRampDownShortSmoothTwo100to0
+            | Effect::TransitionRampDownShortSharpOne100to0
+            | Effect::TransitionRampDownShortSharpTwo100to0
+            | Effect::TransitionRampUpShortSmoothOne0to100
+            | Effect::TransitionRampUpShortSmoothTwo0to100
+            | Effect::TransitionRampUpShortSharpOne0to100
+            | Effect::TransitionRampUpShortSharpTwo0to100
+            | Effect::TransitionRampDownShortSmoothOne50to0
+            | Effect::TransitionRampDownShortSmoothTwo50to0
+            | Effect::TransitionRampDownShortSharpOne50to0
+            | Effect::TransitionRampDownShortSharpTwo50to0
+            | Effect::TransitionRampUpShortSmoothOne0to50
+            | Effect::TransitionRampUpShortSmoothTwo0to50
+            | Effect::TransitionRampUpShortSharpOne0to50
+            | Effect::TransitionRampUpShortSharpTwo0to50 => 150,
+            Effect::ShortDoubleClickStrongOne100
+            | Effect::ShortDoubleClickStrongTwo80
+            | Effect::ShortDoubleClickStrongThree60
+            | Effect::ShortDoubleClickStrongFour30
+            | Effect::ShortDoubleClickMediumOne100
+            | Effect::ShortDoubleClickMediumTwo80
+            | Effect::ShortDoubleClickMediumThree60 => 160,
+            Effect::DoubleClick100 | Effect::DoubleClick60 => 200,
+            Effect::LongDoubleSharpTickOne100
+            | Effect::LongDoubleSharpTickTwo80
+            | Effect::LongDoubleSharpTickThree60 => 250,
+            Effect::TripleClick100
+            | Effect::SoftFuzz60
+            | Effect::StrongBuzz100
+            | Effect::LongDoubleSharpClickStrongOne100
+            | Effect::LongDoubleSharpClickStrongTwo80
+            | Effect::LongDoubleSharpClickStrongThree60
+            | Effect::LongDoubleSharpClickStrongFour30
+            | Effect::LongDoubleSharpClickMediumOne100
+            | Effect::LongDoubleSharpClickMediumTwo80
+            | Effect::LongDoubleSharpClickMediumThree60
+            | Effect::BuzzOne100
+            | Effect::BuzzTwo80
+            | Effect::BuzzThree60
+            | Effect::BuzzFour40
+            | Effect::BuzzFive20 => 300,
+            Effect::TransitionRampDownMediumSmoothOne100to0
+            | Effect::TransitionRampDownMediumSmoothTwo100to0
+            | Effect::TransitionRampDownMediumSharpOne100to0
+            | Effect::TransitionRampDownMediumSharpTwo100to0
+            | Effect::TransitionRampUpMediumSmoothOne0to100
+            | Effect::TransitionRampUpMediumSmoothTwo0to100
+            | Effect::TransitionRampUpMediumSharpOne0to100
+            | Effect::TransitionRampUpMediumSharpTwo0to100
+            | Effect::TransitionRampDownMediumSmoothOne50to0
+            | Effect::TransitionRampDownMediumSmoothTwo50to0
+            | Effect::TransitionRampDownMediumSharpOne50to0
+            | Effect::TransitionRampDownMediumSharpTwo50to0
+            | Effect::TransitionRampUpMediumSmoothOne0to50
+            | Effect::TransitionRampUpMediumSmoothTwo0to50
+            | Effect::TransitionRampUpMediumSharpOne0to50
+            | Effect::TransitionRampUpMediumSharpTwo0to50 => 350,
+            Effect::SmoothHumOne50
+            | Effect::SmoothHumTwo40
+            | Effect::SmoothHumThree30
+            | Effect::SmoothHumFour20
+            | Effect::SmoothHumFive10 => 500,
+            Effect::PulsingStrongOne100
+            | Effect::PulsingStrongTwo60
+            | Effect::PulsingMediumOne100
+            | Effect::PulsingMediumTwo60
+            | Effect::PulsingSharpOne100
+            | Effect::PulsingSharpTwo60
+            | Effect::TransitionRampDownLongSmoothOne100to0
+            | Effect::TransitionRampDownLongSmoothTwo100to0
+            | Effect::TransitionRampDownLongSharpOne100to0
+            | Effect::TransitionRampDownLongSharpTwo100to0
+            | Effect::TransitionRampUpLongSmoothOne0to100
+            | Effect::TransitionRampUpLongSmoothTwo0to100
+            | Effect::TransitionRampUpLongSharpOne0to100
+            | Effect::TransitionRampUpLongSharpTwo0to100
+            | Effect::TransitionRampDownLongSmoothOne50to0
+            | Effect::TransitionRampDownLongSmoothTwo50to0
+            | Effect::TransitionRampDownLongSharpOne50to0
+            | Effect::TransitionRampDownLongSharpTwo50to0
+            | Effect::TransitionRampUpLongSmoothOne0to50
+            | Effect::TransitionRampUpLongSmoothTwo0to50
+            | Effect::TransitionRampUpLongSharpOne0to50
+            | Effect::TransitionRampUpLongSharpTwo0to50 => 600,
+            Effect::Alert750ms => 750,
+            Effect::Alert1000ms | Effect::LongBuzzForProgrammaticStopping100 => 1000,
+        }
+    }
+}
+
 /// Selection of built-in waveforms that can be sequenced using the `set_rom`
 /// and `set_rom_single` function and triggered using the `set_go` function
 #[allow(unused)]
@@ -757,9 +953,11 @@ impl From<u8> for RealTimePlaybackInputReg {
     }
 }
 
+#[cfg(feature = "rom")]
 pub struct Waveform0Reg(u8);
+#[cfg(feature = "rom")]
 impl Register for Waveform0Reg {
-    const ADDRESS: u8 = 0x05;
+    const ADDRESS: u8 = 0x04;
     fn value(&self) -> u8 {
         self.0
     }
@@ -1104,7 +1302,7 @@ bitfield! {
     /// (OTP) memory which programs the contents of register 0x16 through 0x1A
     /// into nonvolatile memory. This process can only be executed one time per
     /// device. See the Programming On-Chip OTP Memory section for details.
-    pub otp_program, set_otp_program: 1;
+    pub otp_program, set_otp_program: 0;
 }
 
 impl From<u8> for Control4Reg {
@@ -1185,3 +1383,287 @@ impl Default for Control5Reg {
         reg
     }
 }
+
+/// This bit provides a real-time reading of the supply voltage at the VDD pin.
+/// The device must be actively sending a waveform to take reading.
+/// VDD (V) = VBAT\[7:0\] × 5.6 V / 255
+#[derive(Debug)]
+pub struct VbatReg(pub u8);
+
+impl Register for VbatReg {
+    const ADDRESS: u8 = 0x21;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for VbatReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(feature = "audio")]
+bitfield! {
+    pub struct AudioToVibeControlReg(u8);
+    impl Debug;
+
+    /// This bit sets the peak detection time for the audio-to-vibe signal path.
+    /// 0: 10 ms
+    /// 1: 20 ms
+    /// 2: 30 ms
+    /// 3: 40 ms
+    pub ath_peak_time, set_ath_peak_time: 3, 2;
+
+    /// This bit sets the low-pass filter frequency for the audio-to-vibe signal
+    /// path.
+    /// 0: 100 Hz
+    /// 1: 125 Hz
+    /// 2: 150 Hz
+    /// 3: 200 Hz
+    pub ath_filter, set_ath_filter: 1, 0;
+}
+
+#[cfg(feature = "audio")]
+impl From<u8> for AudioToVibeControlReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Register for AudioToVibeControlReg {
+    const ADDRESS: u8 = 0x11;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// This bit sets the minimum input level for the audio-to-vibe signal path.
+/// Input level (V) = ATH_MIN_INPUT\[7:0\] × 1.8 V / 255
+#[cfg(feature = "audio")]
+#[derive(Debug)]
+pub struct AudioToVibeMinInputReg(pub u8);
+
+#[cfg(feature = "audio")]
+impl Register for AudioToVibeMinInputReg {
+    const ADDRESS: u8 = 0x12;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+#[cfg(feature = "audio")]
+impl From<u8> for AudioToVibeMinInputReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+/// This bit sets the maximum input level for the audio-to-vibe signal path.
+/// Input level (V) = ATH_MAX_INPUT\[7:0\] × 1.8 V / 255
+#[cfg(feature = "audio")]
+#[derive(Debug)]
+pub struct AudioToVibeMaxInputReg(pub u8);
+
+#[cfg(feature = "audio")]
+impl Register for AudioToVibeMaxInputReg {
+    const ADDRESS: u8 = 0x13;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+#[cfg(feature = "audio")]
+impl From<u8> for AudioToVibeMaxInputReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+/// This bit sets the minimum output drive for the audio-to-vibe signal path.
+/// Output drive (%) = ATH_MIN_DRIVE\[7:0\] / 255 × 100%
+#[cfg(feature = "audio")]
+#[derive(Debug)]
+pub struct AudioToVibeMinDriveReg(pub u8);
+
+#[cfg(feature = "audio")]
+impl Register for AudioToVibeMinDriveReg {
+    const ADDRESS: u8 = 0x14;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+#[cfg(feature = "audio")]
+impl From<u8> for AudioToVibeMinDriveReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+/// This bit sets the maximum output drive for the audio-to-vibe signal path.
+/// Output drive (%) = ATH_MAX_DRIVE\[7:0\] / 255 × 100%
+#[cfg(feature = "audio")]
+#[derive(Debug)]
+pub struct AudioToVibeMaxDriveReg(pub u8);
+
+#[cfg(feature = "audio")]
+impl Register for AudioToVibeMaxDriveReg {
+    const ADDRESS: u8 = 0x15;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+#[cfg(feature = "audio")]
+impl From<u8> for AudioToVibeMaxDriveReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+/// This bit reports the measurement of the LRA resonance period.
+/// LRA period (us) = LRA_PERIOD\[7:0\] × 98.46 µs
+#[derive(Debug)]
+pub struct LraPeriodReg(pub u8);
+
+impl Register for LraPeriodReg {
+    const ADDRESS: u8 = 0x22;
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for LraPeriodReg {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+/// Rom effects indexed by their id less one, as effect ids start at 1
+const ROM_EFFECTS: [Effect; 123] = [
+    Effect::StrongClick100,
+    Effect::StrongClick60,
+    Effect::StrongClick30,
+    Effect::SharpClick100,
+    Effect::SharpClick60,
+    Effect::SharpClick30,
+    Effect::SoftBump100,
+    Effect::SoftBump60,
+    Effect::SoftBump30,
+    Effect::DoubleClick100,
+    Effect::DoubleClick60,
+    Effect::TripleClick100,
+    Effect::SoftFuzz60,
+    Effect::StrongBuzz100,
+    Effect::Alert750ms,
+    Effect::Alert1000ms,
+    Effect::StrongClickOne100,
+    Effect::StrongClickTwo80,
+    Effect::StrongClickThree60,
+    Effect::StrongClickFour30,
+    Effect::MediumClickOne100,
+    Effect::MediumClickTwo80,
+    Effect::MediumClickThree60,
+    Effect::SharpTickOne100,
+    Effect::SharpTickTwo80,
+    Effect::SharpTickThree60,
+    Effect::ShortDoubleClickStrongOne100,
+    Effect::ShortDoubleClickStrongTwo80,
+    Effect::ShortDoubleClickStrongThree60,
+    Effect::ShortDoubleClickStrongFour30,
+    Effect::ShortDoubleClickMediumOne100,
+    Effect::ShortDoubleClickMediumTwo80,
+    Effect::ShortDoubleClickMediumThree60,
+    Effect::ShortDoubleSharpTickOne100,
+    Effect::ShortDoubleSharpTickTwo80,
+    Effect::ShortDoubleSharpTickThree60,
+    Effect::LongDoubleSharpClickStrongOne100,
+    Effect::LongDoubleSharpClickStrongTwo80,
+    Effect::LongDoubleSharpClickStrongThree60,
+    Effect::LongDoubleSharpClickStrongFour30,
+    Effect::LongDoubleSharpClickMediumOne100,
+    Effect::LongDoubleSharpClickMediumTwo80,
+    Effect::LongDoubleSharpClickMediumThree60,
+    Effect::LongDoubleSharpTickOne100,
+    Effect::LongDoubleSharpTickTwo80,
+    Effect::LongDoubleSharpTickThree60,
+    Effect::BuzzOne100,
+    Effect::BuzzTwo80,
+    Effect::BuzzThree60,
+    Effect::BuzzFour40,
+    Effect::BuzzFive20,
+    Effect::PulsingStrongOne100,
+    Effect::PulsingStrongTwo60,
+    Effect::PulsingMediumOne100,
+    Effect::PulsingMediumTwo60,
+    Effect::PulsingSharpOne100,
+    Effect::PulsingSharpTwo60,
+    Effect::TransitionClickOne100,
+    Effect::TransitionClickTwo80,
+    Effect::TransitionClickThree60,
+    Effect::TransitionClickFour40,
+    Effect::TransitionClickFive20,
+    Effect::TransitionClickSix10,
+    Effect::TransitionHumOne100,
+    Effect::TransitionHumTwo80,
+    Effect::TransitionHumThree60,
+    Effect::TransitionHumFour40,
+    Effect::TransitionHumFive20,
+    Effect::TransitionHumSix10,
+    Effect::TransitionRampDownLongSmoothOne100to0,
+    Effect::TransitionRampDownLongSmoothTwo100to0,
+    Effect::TransitionRampDownMediumSmoothOne100to0,
+    Effect::TransitionRampDownMediumSmoothTwo100to0,
+    Effect::TransitionRampDownShortSmoothOne100to0,
+    Effect::TransitionRampDownShortSmoothTwo100to0,
+    Effect::TransitionRampDownLongSharpOne100to0,
+    Effect::TransitionRampDownLongSharpTwo100to0,
+    Effect::TransitionRampDownMediumSharpOne100to0,
+    Effect::TransitionRampDownMediumSharpTwo100to0,
+    Effect::TransitionRampDownShortSharpOne100to0,
+    Effect::TransitionRampDownShortSharpTwo100to0,
+    Effect::TransitionRampUpLongSmoothOne0to100,
+    Effect::TransitionRampUpLongSmoothTwo0to100,
+    Effect::TransitionRampUpMediumSmoothOne0to100,
+    Effect::TransitionRampUpMediumSmoothTwo0to100,
+    Effect::TransitionRampUpShortSmoothOne0to100,
+    Effect::TransitionRampUpShortSmoothTwo0to100,
+    Effect::TransitionRampUpLongSharpOne0to100,
+    Effect::TransitionRampUpLongSharpTwo0to100,
+    Effect::TransitionRampUpMediumSharpOne0to100,
+    Effect::TransitionRampUpMediumSharpTwo0to100,
+    Effect::TransitionRampUpShortSharpOne0to100,
+    Effect::TransitionRampUpShortSharpTwo0to100,
+    Effect::TransitionRampDownLongSmoothOne50to0,
+    Effect::TransitionRampDownLongSmoothTwo50to0,
+    Effect::TransitionRampDownMediumSmoothOne50to0,
+    Effect::TransitionRampDownMediumSmoothTwo50to0,
+    Effect::TransitionRampDownShortSmoothOne50to0,
+    Effect::TransitionRampDownShortSmoothTwo50to0,
+    Effect::TransitionRampDownLongSharpOne50to0,
+    Effect::TransitionRampDownLongSharpTwo50to0,
+    Effect::TransitionRampDownMediumSharpOne50to0,
+    Effect::TransitionRampDownMediumSharpTwo50to0,
+    Effect::TransitionRampDownShortSharpOne50to0,
+    Effect::TransitionRampDownShortSharpTwo50to0,
+    Effect::TransitionRampUpLongSmoothOne0to50,
+    Effect::TransitionRampUpLongSmoothTwo0to50,
+    Effect::TransitionRampUpMediumSmoothOne0to50,
+    Effect::TransitionRampUpMediumSmoothTwo0to50,
+    Effect::TransitionRampUpShortSmoothOne0to50,
+    Effect::TransitionRampUpShortSmoothTwo0to50,
+    Effect::TransitionRampUpLongSharpOne0to50,
+    Effect::TransitionRampUpLongSharpTwo0to50,
+    Effect::TransitionRampUpMediumSharpOne0to50,
+    Effect::TransitionRampUpMediumSharpTwo0to50,
+    Effect::TransitionRampUpShortSharpOne0to50,
+    Effect::TransitionRampUpShortSharpTwo0to50,
+    Effect::LongBuzzForProgrammaticStopping100,
+    Effect::SmoothHumOne50,
+    Effect::SmoothHumTwo40,
+    Effect::SmoothHumThree30,
+    Effect::SmoothHumFour20,
+    Effect::SmoothHumFive10,
+];
@@ -0,0 +1,159 @@
+//! A fake drv2605l register file and clock for driving `Drv2605l` on the
+//! host. Each test crate only uses some of it.
+#![allow(dead_code)]
+
+use core::convert::Infallible;
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use drv2605l::{Calibration, Drv2605l, LoadParams};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+pub const MODE: u8 = 0x01;
+pub const GO: u8 = 0x0C;
+pub const CONTROL4: u8 = 0x1E;
+
+/// How long GO keeps reading back set after it's written, however short the
+/// playback
+pub const TRANSIENT_US: u64 = 1000;
+
+/// A register file that plays GO for `playback_us` and burns the OTP
+/// immediately, sharing a microsecond clock with `FakeDelay`
+pub struct FakeDevice {
+    pub regs: [u8; 0x23],
+    now: Rc<Cell<u64>>,
+    playback_us: u64,
+    go_until: u64,
+    pub go_written_at: Option<u64>,
+    /// Every GO readback after the first GO, with when it happened
+    pub go_reads: Vec<(u64, bool)>,
+    /// Every (address, value) written, in order
+    pub writes: Vec<(u8, u8)>,
+    /// Every address read, in order
+    pub reads: Vec<u8>,
+}
+
+impl FakeDevice {
+    pub fn new(now: Rc<Cell<u64>>, playback_us: u64) -> Self {
+        let mut regs = [0u8; 0x23];
+        // device id 7, the drv2605l
+        regs[0x00] = 0xE0;
+        regs[MODE as usize] = 0x40;
+        Self {
+            regs,
+            now,
+            playback_us,
+            go_until: 0,
+            go_written_at: None,
+            go_reads: Vec::new(),
+            writes: Vec::new(),
+            reads: Vec::new(),
+        }
+    }
+
+    fn write(&mut self, address: u8, value: u8) {
+        let now = self.now.get();
+        self.writes.push((address, value));
+        match address {
+            // the reset completes immediately
+            MODE => self.regs[MODE as usize] = value & !0x80,
+            GO if value & 1 == 1 => {
+                self.go_until = now + self.playback_us.max(TRANSIENT_US);
+                self.go_written_at = Some(now);
+            }
+            GO => self.go_until = now,
+            // OTP_PROGRAM burns the otp, reported in OTP_STATUS
+            CONTROL4 if value & 0x01 == 0x01 => self.regs[CONTROL4 as usize] = value | 0x04,
+            _ => self.regs[address as usize] = value,
+        }
+    }
+
+    fn read(&mut self, address: u8) -> u8 {
+        self.reads.push(address);
+        match address {
+            GO => {
+                let now = self.now.get();
+                let go = now < self.go_until;
+                if self.go_written_at.is_some() {
+                    self.go_reads.push((now, go));
+                }
+                go as u8
+            }
+            _ => self.regs[address as usize],
+        }
+    }
+}
+
+impl ErrorType for FakeDevice {
+    type Error = Infallible;
+}
+
+impl I2c for FakeDevice {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut pointer = 0;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    pointer = bytes[0];
+                    for &value in &bytes[1..] {
+                        self.write(pointer, value);
+                        pointer += 1;
+                    }
+                }
+                Operation::Read(buf) => {
+                    for value in buf.iter_mut() {
+                        *value = self.read(pointer);
+                        pointer += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Advances the shared clock instead of sleeping
+pub struct FakeDelay(Rc<Cell<u64>>);
+
+impl DelayNs for FakeDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + u64::from(ns).div_ceil(1000));
+    }
+}
+
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// An ERM driver on a `FakeDevice` whose GO playback lasts `playback_us`,
+/// loaded with fixed calibration results
+pub fn driver(playback_us: u64) -> Drv2605l<FakeDevice, Infallible, FakeDelay> {
+    let now = Rc::new(Cell::new(0));
+    let device = FakeDevice::new(now.clone(), playback_us);
+    let load = LoadParams {
+        compenstation: 0x0C,
+        back_emf: 0x6C,
+        back_emf_gain: 2,
+    };
+    block_on(Drv2605l::new(
+        device,
+        FakeDelay(now),
+        Calibration::Load(load),
+        false,
+    ))
+    .unwrap()
+}
@@ -0,0 +1,36 @@
+//! Reproduces the GO readback race against a fake device: right after a GO
+//! the bit reads back set for about a millisecond even when the effect is
+//! over at once, so checking it straight away misreports playback.
+
+mod common;
+
+use common::{block_on, driver, TRANSIENT_US};
+
+#[test]
+fn go_reads_set_right_after_an_instant_effect() {
+    let mut haptic = driver(0);
+    block_on(haptic.set_go()).unwrap();
+    assert!(block_on(haptic.go()).unwrap());
+}
+
+#[test]
+fn set_go_confirmed_settles_past_the_transient() {
+    let mut haptic = driver(0);
+    assert!(!block_on(haptic.set_go_confirmed()).unwrap());
+
+    let (device, _, _) = haptic.release();
+    let written_at = device.go_written_at.unwrap();
+    // the read-modify-write of the GO bit happens before it's set, so only
+    // the confirming readback follows and it waits out the transient
+    assert_eq!(device.go_reads.len(), 1);
+    let (read_at, go) = device.go_reads[0];
+    assert!(read_at - written_at >= TRANSIENT_US);
+    assert!(!go);
+}
+
+#[test]
+fn set_go_confirmed_reports_ongoing_playback() {
+    let mut haptic = driver(50_000);
+    assert!(block_on(haptic.set_go_confirmed()).unwrap());
+    assert!(block_on(haptic.go()).unwrap());
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::{block_on, driver, CONTROL4};
+
+#[test]
+fn program_otp_sets_otp_program_and_checks_status() {
+    let mut haptic = driver(0);
+    block_on(haptic.program_otp()).unwrap();
+
+    let (device, _, _) = haptic.release();
+    let burn = device
+        .writes
+        .iter()
+        .position(|&write| write == (CONTROL4, 0x01))
+        .unwrap();
+    assert_eq!(device.writes.len(), burn + 1);
+    // the status check and the read-modify-write before the burn, then a
+    // single OTP_STATUS readback after it
+    let status_reads = device.reads.iter().filter(|&&address| address == CONTROL4);
+    assert_eq!(status_reads.count(), 3);
+    assert_eq!(device.reads.last(), Some(&CONTROL4));
+}
+
+#[test]
+fn restore_config_never_replays_otp_program() {
+    let mut haptic = driver(0);
+    let mut snapshot = block_on(haptic.snapshot_config()).unwrap();
+    snapshot.control[3] |= 0x01;
+    block_on(haptic.restore_config(&snapshot)).unwrap();
+
+    let (device, _, _) = haptic.release();
+    let ctrl4 = device
+        .writes
+        .iter()
+        .filter(|(address, _)| *address == CONTROL4)
+        .map(|(_, value)| *value);
+    assert_eq!(ctrl4.collect::<Vec<_>>(), [snapshot.control[3] & !0x01]);
+}
@@ -0,0 +1,77 @@
+use drv2605l::{CalibrationParams, ParamError};
+
+#[test]
+fn erm_defaults_image() {
+    let image = CalibrationParams::default_for(false).to_register_image(false);
+    assert_eq!(
+        image,
+        [
+            (0x16, 0x3E),
+            (0x17, 0x8C),
+            (0x1A, 0x2A),
+            (0x1B, 0x93),
+            (0x1C, 0xF5),
+            (0x1E, 0x30),
+        ]
+    );
+}
+
+#[test]
+fn lra_defaults_image() {
+    let image = CalibrationParams::default_for(true).to_register_image(true);
+    assert_eq!(
+        image,
+        [
+            (0x16, 0x53),
+            (0x17, 0x89),
+            (0x1A, 0xAA),
+            (0x1B, 0x97),
+            (0x1C, 0xF5),
+            (0x1E, 0x30),
+        ]
+    );
+}
+
+#[test]
+fn image_round_trips() {
+    let params = CalibrationParams::builder()
+        .rated_voltage(0x71)
+        .overdrive_voltage_clamp(0x96)
+        .drive_time(0x1F)
+        .brake_factor(7)
+        .loop_gain(1)
+        .auto_cal_time(0)
+        .lra_sample_time(2)
+        .lra_blanking_time(3)
+        .lra_idiss_time(0)
+        .lra_zc_det_time(3)
+        .build()
+        .unwrap();
+
+    for lra in [false, true] {
+        let image = params.to_register_image(lra);
+        let (parsed, parsed_lra) = CalibrationParams::from_register_image(&image).unwrap();
+        assert_eq!(parsed_lra, lra);
+        assert_eq!(parsed.rated_voltage, 0x71);
+        assert_eq!(parsed.overdrive_voltage_clamp, 0x96);
+        assert_eq!(parsed.drive_time, 0x1F);
+        assert_eq!(parsed.brake_factor, 7);
+        assert_eq!(parsed.loop_gain, 1);
+        assert_eq!(parsed.auto_cal_time, 0);
+        assert_eq!(parsed.lra_sample_time, 2);
+        assert_eq!(parsed.lra_blanking_time, 3);
+        assert_eq!(parsed.lra_idiss_time, 0);
+        assert_eq!(parsed.lra_zc_det_time, 3);
+        assert_eq!(parsed.to_register_image(lra), image);
+    }
+}
+
+#[test]
+fn image_out_of_order_rejected() {
+    let mut image = CalibrationParams::default().to_register_image(false);
+    image.swap(0, 1);
+    assert!(matches!(
+        CalibrationParams::from_register_image(&image),
+        Err(ParamError::InvalidParameter)
+    ));
+}